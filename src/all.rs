@@ -0,0 +1,150 @@
+use crate::day::{DayRunner, RunOptions};
+use crate::report::DayReport;
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
+
+/// a day the all command is going to run
+pub struct Job {
+    pub day: u32,
+    pub runner: DayRunner,
+    pub options: RunOptions,
+}
+
+/// runs the jobs on up to concurrency threads and hands each report to done in job order,
+/// as soon as it and every job before it have finished
+pub fn run_ordered(jobs: Vec<Job>, concurrency: usize, mut done: impl FnMut(DayReport)) {
+    let next = AtomicUsize::new(0);
+    let (send, recv) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..concurrency.clamp(1, jobs.len().max(1)) {
+            let send = send.clone();
+            let (jobs, next) = (&jobs, &next);
+
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(i) else {
+                    break;
+                };
+
+                if send
+                    .send((i, job.runner.run(job.day, &job.options)))
+                    .is_err()
+                {
+                    break;
+                }
+            });
+        }
+        drop(send);
+
+        // reports that finished before an earlier day did
+        let mut waiting = BTreeMap::new();
+        let mut printed = 0;
+
+        for (i, report) in recv {
+            waiting.insert(i, report);
+
+            while let Some(report) = waiting.remove(&printed) {
+                done(report);
+                printed += 1;
+            }
+        }
+    });
+}
+
+/// cpu time used by this process so far, over all threads
+#[cfg(unix)]
+pub fn cpu_time() -> Option<Duration> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+
+    // safety: getrusage only writes to the struct it is given
+    if unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let usage = unsafe { usage.assume_init() };
+
+    let time = |t: libc::timeval| {
+        Duration::from_secs(t.tv_sec as u64) + Duration::from_micros(t.tv_usec as u64)
+    };
+
+    Some(time(usage.ru_utime) + time(usage.ru_stime))
+}
+
+#[cfg(not(unix))]
+pub fn cpu_time() -> Option<Duration> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::BenchOptions;
+    use crate::day::RunDay;
+    use crate::error::AocError;
+    use crate::input::MemorySource;
+    use crate::select::Parts;
+    use std::sync::Arc;
+
+    // sleeps (5 - day) * 10ms so later days finish first
+    struct Sleepy;
+
+    impl RunDay for Sleepy {
+        fn run(&'static self, number: u32, _options: &RunOptions) -> DayReport {
+            thread::sleep(Duration::from_millis(10 * (5 - number as u64)));
+            DayReport::new(2024, number)
+        }
+
+        fn exe(
+            &self,
+            _number: u32,
+            _options: &RunOptions,
+            _args: &[String],
+        ) -> Result<(), AocError> {
+            Ok(())
+        }
+
+        fn repl(&self, _number: u32, _options: &RunOptions) -> Result<(), AocError> {
+            Ok(())
+        }
+
+        fn bench(
+            &self,
+            _number: u32,
+            _options: &RunOptions,
+            _bench: &BenchOptions,
+        ) -> Result<(), AocError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn reports_in_day_order() {
+        let jobs = (1..=4)
+            .map(|day| Job {
+                day,
+                runner: &Sleepy,
+                options: RunOptions {
+                    year: 2024,
+                    verbosity: 0,
+                    params: Vec::new(),
+                    input: None,
+                    timeout: None,
+                    parts: Parts::Both,
+                    source: Arc::new(MemorySource::default()),
+                },
+            })
+            .collect();
+
+        let mut days = Vec::new();
+        run_ordered(jobs, 4, |report| days.push(report.day));
+
+        assert_eq!(days, vec![1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,165 @@
+// without the alloc-stats feature the allocator is only used by the tests
+#![cfg_attr(not(feature = "alloc-stats"), allow(dead_code))]
+
+use serde::Serialize;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering::Relaxed},
+};
+
+/// the system allocator, counting every allocation, only installed with the alloc-stats feature
+/// since the shared counters slow down allocation heavy (and multithreaded) days
+pub struct Counting;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOC: Counting = Counting;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+fn counted(size: usize) {
+    ALLOCATIONS.fetch_add(1, Relaxed);
+    BYTES.fetch_add(size as u64, Relaxed);
+    let live = LIVE.fetch_add(size, Relaxed) + size;
+    PEAK.fetch_max(live, Relaxed);
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            counted(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            counted(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Relaxed);
+    }
+
+    // a realloc counts as a new allocation of the new size replacing the old one
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            LIVE.fetch_sub(layout.size(), Relaxed);
+            counted(new_size);
+        }
+        new
+    }
+}
+
+/// memory use of one phase, peak is the most bytes that were live at once
+/// on top of what was already live when the phase started
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    pub peak_bytes: usize,
+}
+
+/// runs f and counts what it allocated, None without the alloc-stats feature.
+/// the counters are process wide, so with --jobs above 1 they include the other running days
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !cfg!(feature = "alloc-stats") {
+        return (f(), None);
+    }
+
+    let (res, stats) = count(f);
+    (res, Some(stats))
+}
+
+fn count<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let bytes = BYTES.load(Relaxed);
+    let live = LIVE.load(Relaxed);
+    PEAK.store(live, Relaxed);
+
+    let res = f();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+        bytes: BYTES.load(Relaxed) - bytes,
+        peak_bytes: PEAK.load(Relaxed).saturating_sub(live),
+    };
+    (res, stats)
+}
+
+struct Bytes(u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut size = self.0 as f64;
+        for unit in ["B", "KiB", "MiB"] {
+            if size < 1024.0 {
+                return if unit == "B" {
+                    write!(f, "{} {unit}", self.0)
+                } else {
+                    write!(f, "{size:.1} {unit}")
+                };
+            }
+            size /= 1024.0;
+        }
+        write!(f, "{size:.1} GiB")
+    }
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            Bytes(self.bytes),
+            Bytes(self.peak_bytes as u64)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_sizes() {
+        assert_eq!(Bytes(12).to_string(), "12 B");
+        assert_eq!(Bytes(1536).to_string(), "1.5 KiB");
+        assert_eq!(Bytes(3 << 30).to_string(), "3.0 GiB");
+    }
+
+    // with the feature on every other test thread is counted too, so only check exact numbers without it
+    #[cfg(not(feature = "alloc-stats"))]
+    #[test]
+    fn counts_allocations() {
+        let layout = |size| Layout::from_size_align(size, 8).unwrap();
+
+        let ((), stats) = count(|| unsafe {
+            let a = Counting.alloc(layout(100));
+            let b = Counting.alloc(layout(50));
+            Counting.dealloc(a, layout(100));
+            let b = Counting.realloc(b, layout(50), 200);
+            Counting.dealloc(b, layout(200));
+        });
+
+        assert_eq!(
+            stats,
+            AllocStats {
+                allocations: 3,
+                bytes: 350,
+                peak_bytes: 200,
+            }
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use core::fmt;
+use num::{BigInt, Signed, ToPrimitive};
+
+/// the value produced by a part, integers that fit in an i64 are always stored as Int
+/// so that answers built from different integer types compare equal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    BigInt(BigInt),
+    Str(String),
+    Coord(i64, i64),
+    NotImplemented,
+}
+
+impl Answer {
+    pub fn is_implemented(&self) -> bool {
+        *self != Answer::NotImplemented
+    }
+
+    /// puzzle answers are positive numbers or non empty strings,
+    /// anything else is almost certainly a bug in the solution
+    pub fn is_plausible(&self) -> bool {
+        match self {
+            Answer::Int(n) => *n > 0,
+            Answer::BigInt(n) => n.is_positive(),
+            Answer::Str(s) => !s.is_empty(),
+            Answer::Coord(x, y) => *x >= 0 && *y >= 0,
+            Answer::NotImplemented => true,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::BigInt(n) => write!(f, "{n}"),
+            Answer::Str(s) => write!(f, "{s}"),
+            Answer::Coord(x, y) => write!(f, "{x},{y}"),
+            Answer::NotImplemented => write!(f, "not yet implemented"),
+        }
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Answer {
+        match n.to_i64() {
+            Some(n) => Answer::Int(n),
+            None => Answer::BigInt(n),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Answer {
+                match i64::try_from(n) {
+                    Ok(n) => Answer::Int(n),
+                    Err(_) => Answer::BigInt(BigInt::from(n)),
+                }
+            }
+        })*
+    };
+}
+
+from_int!(i32, i64, i128, u32, u64, u128, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Str(s.to_string())
+    }
+}
+
+impl<T: Into<i64>> From<(T, T)> for Answer {
+    fn from((x, y): (T, T)) -> Answer {
+        Answer::Coord(x.into(), y.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_compare_across_types() {
+        assert_eq!(Answer::from(42u32), Answer::from(42i64));
+        assert_eq!(Answer::from(BigInt::from(42)), Answer::Int(42));
+        assert_eq!(
+            Answer::from(u64::MAX),
+            Answer::BigInt(BigInt::from(u64::MAX))
+        );
+    }
+
+    #[test]
+    fn plausibility() {
+        assert!(Answer::Int(1).is_plausible());
+        assert!(!Answer::Int(0).is_plausible());
+        assert!(!Answer::Str(String::new()).is_plausible());
+    }
+}
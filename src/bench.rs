@@ -0,0 +1,134 @@
+use core::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy)]
+pub struct BenchOptions {
+    pub warmup: u32,
+    pub iterations: u32,
+    /// time allowed for a whole day, split evenly between parsing and the parts that run
+    pub budget: Option<Duration>,
+}
+
+impl BenchOptions {
+    /// the share of the budget each of phases gets
+    fn phase_budget(&self, phases: u32) -> Option<Duration> {
+        self.budget.map(|budget| budget / phases.max(1))
+    }
+}
+
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+
+        let n = samples.len();
+        let secs = samples.iter().map(|d| d.as_secs_f64()).collect::<Vec<_>>();
+        let mean = secs.iter().sum::<f64>() / n as f64;
+        let variance = secs.iter().map(|s| (s - mean) * (s - mean)).sum::<f64>() / n as f64;
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+
+        Stats {
+            samples: n,
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:>10.2?}  median {:>10.2?}  mean {:>10.2?} ± {:<10.2?} ({} runs)",
+            self.min, self.median, self.mean, self.stddev, self.samples
+        )
+    }
+}
+
+/// runs f options.warmup times untimed, then times it up to options.iterations times,
+/// stopping early (after at least one sample) once this phase's share of the budget is used up.
+/// phases is how many phases the budget is split between
+pub fn sample<T, E>(
+    options: &BenchOptions,
+    phases: u32,
+    mut f: impl FnMut() -> Result<T, E>,
+) -> Result<(T, Stats), E> {
+    let budget = options.phase_budget(phases);
+    let start = Instant::now();
+
+    for _ in 0..options.warmup {
+        f()?;
+    }
+
+    let mut samples = Vec::with_capacity(options.iterations as usize);
+    let mut last;
+
+    loop {
+        let now = Instant::now();
+        last = f()?;
+        samples.push(now.elapsed());
+
+        let out_of_time = budget.is_some_and(|budget| start.elapsed() >= budget);
+        if samples.len() >= options.iterations as usize || out_of_time {
+            break;
+        }
+    }
+
+    Ok((last, Stats::from_samples(samples)))
+}
+
+/// clap value parser for a duration given in (fractional) seconds
+pub fn parse_seconds(arg: &str) -> Result<Duration, String> {
+    let secs = arg
+        .parse::<f64>()
+        .map_err(|err| format!("invalid number of seconds {arg:?}: {err}"))?;
+
+    Duration::try_from_secs_f64(secs).map_err(|err| format!("invalid duration {arg:?}: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let stats = Stats::from_samples(
+            [4, 1, 3, 2]
+                .into_iter()
+                .map(Duration::from_millis)
+                .collect(),
+        );
+
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert!((stats.stddev.as_secs_f64() - 1.25e-6f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn budget_split() {
+        let options = BenchOptions {
+            warmup: 0,
+            iterations: 10,
+            budget: Some(Duration::from_secs(6)),
+        };
+
+        assert_eq!(options.phase_budget(3), Some(Duration::from_secs(2)));
+        assert_eq!(options.phase_budget(2), Some(Duration::from_secs(3)));
+        assert_eq!(options.phase_budget(1), Some(Duration::from_secs(6)));
+    }
+}
@@ -0,0 +1,300 @@
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::report::DayReport;
+use colored::{ColoredString, Colorize};
+use core::fmt;
+use serde::Deserialize;
+use std::{collections::HashMap, fs, path::Path, time::Duration};
+
+/// changes smaller than this are noise whatever the percentage
+const NOISE: Duration = Duration::from_micros(100);
+
+#[derive(Deserialize)]
+struct SavedPart {
+    answer: Option<serde_json::Value>,
+    time_ns: Option<u64>,
+}
+
+/// a day as --format json (and so --save) writes it
+#[derive(Deserialize)]
+struct SavedDay {
+    year: u32,
+    day: u32,
+    parse_time_ns: Option<u64>,
+    parts: [SavedPart; 2],
+    error: Option<String>,
+}
+
+/// the results of an earlier run of all
+pub struct Baseline {
+    days: HashMap<(u32, u32), SavedDay>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Baseline, AocError> {
+        let text = fs::read_to_string(path).map_err(|err| AocError::io(path, err))?;
+        let days = serde_json::from_str::<Vec<SavedDay>>(&text).map_err(|err| {
+            AocError::Config(format!(
+                "{} is not a saved run (from all --save or --format json): {err}",
+                path.display()
+            ))
+        })?;
+
+        Ok(Baseline {
+            days: days
+                .into_iter()
+                .map(|day| ((day.year, day.day), day))
+                .collect(),
+        })
+    }
+}
+
+/// writes the reports in the same form as --format json
+pub fn save(path: &Path, reports: &[DayReport]) -> Result<(), AocError> {
+    let json = serde_json::to_string_pretty(reports).expect("reports always serialize");
+    fs::write(path, json + "\n").map_err(|err| AocError::io(path, err))
+}
+
+enum Note {
+    Slower(f64),
+    Faster(f64),
+    AnswerChanged(String),
+    NewlyFailing(String),
+    Fixed,
+    NotInBaseline,
+}
+
+struct Row {
+    day: u32,
+    phase: &'static str,
+    before: Option<Duration>,
+    after: Option<Duration>,
+    note: Option<Note>,
+}
+
+/// how a run differs from a baseline, displayed as a table
+pub struct Comparison {
+    rows: Vec<Row>,
+}
+
+fn answer_text(answer: &serde_json::Value) -> String {
+    match answer {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(xy) => xy
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+        answer => answer.to_string(),
+    }
+}
+
+fn timing(before: Option<Duration>, after: Option<Duration>, threshold: f64) -> Option<Note> {
+    let (before, after) = (before?, after?);
+    if before.abs_diff(after) < NOISE || before.is_zero() {
+        return None;
+    }
+
+    let change = after.as_secs_f64() / before.as_secs_f64() - 1.0;
+    if change > threshold {
+        Some(Note::Slower(change))
+    } else if change < -threshold {
+        Some(Note::Faster(change))
+    } else {
+        None
+    }
+}
+
+/// threshold is the fraction (0.1 for 10%) a phase may get slower before it counts as a regression
+pub fn compare(baseline: &Baseline, reports: &[DayReport], threshold: f64) -> Comparison {
+    let mut rows = Vec::new();
+
+    for report in reports {
+        let Some(saved) = baseline.days.get(&(report.year, report.day)) else {
+            rows.push(Row {
+                day: report.day,
+                phase: "day",
+                before: None,
+                after: None,
+                note: Some(Note::NotInBaseline),
+            });
+            continue;
+        };
+
+        let failing = match (&saved.error, &report.error) {
+            (None, Some(err)) => Some(Note::NewlyFailing(err.to_string())),
+            (Some(_), None) => Some(Note::Fixed),
+            _ => None,
+        };
+        if failing.is_some() {
+            rows.push(Row {
+                day: report.day,
+                phase: "day",
+                before: None,
+                after: None,
+                note: failing,
+            });
+        }
+
+        let before = saved.parse_time_ns.map(Duration::from_nanos);
+        rows.push(Row {
+            day: report.day,
+            phase: "parse",
+            before,
+            after: report.parse_time,
+            note: timing(before, report.parse_time, threshold),
+        });
+
+        for ((phase, saved), part) in ["a", "b"].into_iter().zip(&saved.parts).zip(&report.parts) {
+            let answers = saved.answer.as_ref().zip(
+                part.answer
+                    .as_ref()
+                    .filter(|answer| answer.is_implemented()),
+            );
+            let changed = answers.and_then(|(before, after): (_, &Answer)| {
+                let same = serde_json::to_value(after).is_ok_and(|after| after == *before);
+                (!same).then(|| Note::AnswerChanged(answer_text(before)))
+            });
+
+            let before = saved.time_ns.map(Duration::from_nanos);
+            rows.push(Row {
+                day: report.day,
+                phase,
+                before,
+                after: part.time,
+                note: changed.or_else(|| timing(before, part.time, threshold)),
+            });
+        }
+    }
+
+    Comparison { rows }
+}
+
+impl Comparison {
+    /// rows that got worse: slower phases, changed answers and days that started failing
+    pub fn regressions(&self) -> usize {
+        self.rows
+            .iter()
+            .filter(|row| {
+                matches!(
+                    row.note,
+                    Some(Note::Slower(_) | Note::AnswerChanged(_) | Note::NewlyFailing(_))
+                )
+            })
+            .count()
+    }
+}
+
+impl fmt::Display for Note {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let note: ColoredString = match self {
+            Note::Slower(change) => format!("{:+.0}% slower", change * 100.0).red(),
+            Note::Faster(change) => format!("{:+.0}% faster", change * 100.0).green(),
+            Note::AnswerChanged(before) => format!("answer changed, was {before}").red(),
+            Note::NewlyFailing(err) => format!("newly failing: {err}").red(),
+            Note::Fixed => "no longer failing".green(),
+            Note::NotInBaseline => "not in the baseline".yellow(),
+        };
+        write!(f, "{note}")
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = |time: Option<Duration>| match time {
+            Some(time) => format!("{time:.2?}"),
+            None => "-".to_string(),
+        };
+
+        writeln!(
+            f,
+            "{}",
+            "======= Compared to baseline ========".bright_red()
+        )?;
+        writeln!(
+            f,
+            "{:>3}  {:<5}  {:>12}  {:>12}  note",
+            "day", "phase", "before", "after"
+        )?;
+        for row in self.rows.iter() {
+            write!(
+                f,
+                "{:>3}  {:<5}  {:>12}  {:>12}",
+                row.day,
+                row.phase,
+                time(row.before),
+                time(row.after)
+            )?;
+            match &row.note {
+                Some(note) => writeln!(f, "  {note}")?,
+                None => writeln!(f)?,
+            }
+        }
+        write!(f, "regressions: {}", self.regressions())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Status;
+
+    fn report(day: u32, parse: u64, a: i64, time_a: u64) -> DayReport {
+        let mut report = DayReport::new(2024, day);
+        report.parse_time = Some(Duration::from_millis(parse));
+        report.parts[0].status = Status::Ok;
+        report.parts[0].answer = Some(Answer::Int(a));
+        report.parts[0].time = Some(Duration::from_millis(time_a));
+        report
+    }
+
+    #[test]
+    fn flags_regressions() {
+        let before = [report(1, 10, 5, 10), report(2, 10, 7, 10)];
+        let text = serde_json::to_string(&before).unwrap();
+        let baseline = Baseline {
+            days: serde_json::from_str::<Vec<SavedDay>>(&text)
+                .unwrap()
+                .into_iter()
+                .map(|day| ((day.year, day.day), day))
+                .collect(),
+        };
+
+        let mut failing = report(2, 5, 7, 10);
+        failing.error = Some(AocError::Solve("no path".to_string()));
+        let after = [report(1, 12, 6, 30), failing, report(3, 1, 1, 1)];
+
+        let comparison = compare(&baseline, &after, 0.25);
+        let notes = comparison
+            .rows
+            .iter()
+            .map(|row| {
+                let note = match &row.note {
+                    Some(Note::Slower(_)) => "slower",
+                    Some(Note::Faster(_)) => "faster",
+                    Some(Note::AnswerChanged(_)) => "answer",
+                    Some(Note::NewlyFailing(_)) => "failing",
+                    Some(Note::Fixed) => "fixed",
+                    Some(Note::NotInBaseline) => "new",
+                    None => "",
+                };
+                (row.day, row.phase, note)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            notes,
+            vec![
+                (1, "parse", ""),
+                (1, "a", "answer"),
+                (1, "b", ""),
+                (2, "day", "failing"),
+                (2, "parse", "faster"),
+                (2, "a", ""),
+                (2, "b", ""),
+                (3, "day", "new"),
+            ]
+        );
+        assert_eq!(comparison.regressions(), 2);
+    }
+}
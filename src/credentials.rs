@@ -0,0 +1,384 @@
+use crate::day::FetchConfig;
+use crate::error::AocError;
+use core::fmt;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// the file set-fetch-config used to write to the working directory
+pub const LEGACY_PATH: &str = "AOC_FETCH_CONFIG";
+
+/// sent when AOC_SESSION is used without AOC_USER_AGENT
+const DEFAULT_AGENT: &str = "github.com/astaugaard/rust_aoc_template";
+
+/// where a fetch config came from
+#[derive(Debug, PartialEq, Eq)]
+pub enum Source {
+    Env,
+    Xdg(PathBuf),
+    Legacy(PathBuf),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Env => write!(f, "the AOC_SESSION environment variable"),
+            Source::Xdg(path) => write!(f, "{}", path.display()),
+            Source::Legacy(path) => write!(f, "{} (legacy)", path.display()),
+        }
+    }
+}
+
+/// a fetch config that was set somewhere, the config is Err with what is wrong with it
+pub struct Found {
+    pub source: Source,
+    pub config: Result<FetchConfig, Vec<String>>,
+    /// things that do not stop the config from working but should be fixed
+    pub warnings: Vec<String>,
+}
+
+/// the configs that are set, the first one is used
+pub static FETCH_CONFIGS: Lazy<Vec<Found>> =
+    Lazy::new(|| find_all(|name| env::var(name).ok(), Path::new(LEGACY_PATH)));
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    session: String,
+    agent: String,
+    year: Option<u32>,
+}
+
+/// what is wrong with a session cookie that stops it from working, empty if nothing is
+pub fn session_problems(session: &str) -> Vec<String> {
+    if session.trim().is_empty() {
+        vec!["the session cookie is empty".to_string()]
+    } else if session.trim().starts_with("session=") {
+        vec!["the session cookie starts with session=, leave that out".to_string()]
+    } else {
+        Vec::new()
+    }
+}
+
+/// what looks off about a session cookie, the site may change its format so these only warn
+pub fn session_warnings(session: &str) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    if !session_problems(session).is_empty() {
+        return warnings;
+    }
+    if session.trim() != session {
+        warnings.push("the session cookie has spaces around it".to_string());
+    }
+    if !session.trim().chars().all(|c| c.is_ascii_hexdigit()) {
+        warnings.push("the session cookie has characters that are not hex digits".to_string());
+    }
+    if session.trim().len() != 128 {
+        warnings.push(format!(
+            "the session cookie is {} characters long, usually it is 128",
+            session.trim().len()
+        ));
+    }
+    warnings
+}
+
+// the session warnings of a config that parsed
+fn config_warnings(config: &Result<FetchConfig, Vec<String>>) -> Vec<String> {
+    config
+        .as_ref()
+        .map_or_else(|_| Vec::new(), |config| session_warnings(&config.oauthkey))
+}
+
+fn checked(config: FetchConfig, mut problems: Vec<String>) -> Result<FetchConfig, Vec<String>> {
+    problems.extend(session_problems(&config.oauthkey));
+    if config.agent.trim().is_empty() {
+        problems.push("the user agent is empty".to_string());
+    }
+
+    if problems.is_empty() {
+        Ok(config)
+    } else {
+        Err(problems)
+    }
+}
+
+fn from_env(var: &impl Fn(&str) -> Option<String>) -> Option<Found> {
+    let session = var("AOC_SESSION")?;
+    let config = FetchConfig {
+        year: None,
+        agent: var("AOC_USER_AGENT").unwrap_or_else(|| DEFAULT_AGENT.to_string()),
+        oauthkey: session,
+    };
+
+    let config = checked(config, Vec::new());
+    Some(Found {
+        source: Source::Env,
+        warnings: config_warnings(&config),
+        config,
+    })
+}
+
+/// $XDG_CONFIG_HOME/aoc/config.toml, or ~/.config/aoc/config.toml when it is not set
+pub fn xdg_path(var: impl Fn(&str) -> Option<String>) -> Option<PathBuf> {
+    let config_home = var("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(config_home.join("aoc").join("config.toml"))
+}
+
+fn parse_xdg(text: &str) -> Result<FetchConfig, Vec<String>> {
+    let file = toml::from_str::<ConfigFile>(text).map_err(|err| vec![err.message().to_string()])?;
+    checked(
+        FetchConfig {
+            year: file.year,
+            agent: file.agent,
+            oauthkey: file.session,
+        },
+        Vec::new(),
+    )
+}
+
+// agent, session and year on their own lines
+fn parse_legacy(text: &str) -> Result<FetchConfig, Vec<String>> {
+    let mut lines = text.lines();
+    let (Some(agent), Some(session), Some(year)) = (lines.next(), lines.next(), lines.next())
+    else {
+        return Err(vec![
+            "it should have 3 lines: the user agent, the session cookie and the year".to_string(),
+        ]);
+    };
+
+    let mut problems = Vec::new();
+    let year = match year.trim().parse::<u32>() {
+        Ok(year) => Some(year),
+        Err(_) => {
+            problems.push(format!("the year on line 3 is {year:?}, not a number"));
+            None
+        }
+    };
+
+    checked(
+        FetchConfig {
+            year,
+            agent: agent.to_string(),
+            oauthkey: session.to_string(),
+        },
+        problems,
+    )
+}
+
+#[cfg(unix)]
+fn readable_by_others(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|meta| meta.permissions().mode() & 0o077 != 0)
+}
+
+#[cfg(not(unix))]
+fn readable_by_others(_: &Path) -> bool {
+    false
+}
+
+fn from_file(
+    source: Source,
+    path: &Path,
+    parse: fn(&str) -> Result<FetchConfig, Vec<String>>,
+) -> Option<Found> {
+    let config = match fs::read_to_string(path) {
+        Ok(text) => parse(&text),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return None,
+        Err(err) => Err(vec![format!("could not read it: {err}")]),
+    };
+
+    let mut warnings = config_warnings(&config);
+    if readable_by_others(path) {
+        warnings.push(format!(
+            "other users can read it, chmod 600 {}",
+            path.display()
+        ));
+    }
+    if matches!(source, Source::Legacy(_)) {
+        warnings.push(
+            "it keeps the cookie in the working directory, move it with set-fetch-config"
+                .to_string(),
+        );
+    }
+
+    Some(Found {
+        source,
+        config,
+        warnings,
+    })
+}
+
+/// every fetch config that is set, in the order they are used:
+/// AOC_SESSION, the xdg config file, then the legacy file
+pub fn find_all(var: impl Fn(&str) -> Option<String>, legacy: &Path) -> Vec<Found> {
+    let xdg = xdg_path(&var);
+
+    from_env(&var)
+        .into_iter()
+        .chain(xdg.and_then(|path| from_file(Source::Xdg(path.clone()), &path, parse_xdg)))
+        .chain(from_file(
+            Source::Legacy(legacy.to_path_buf()),
+            legacy,
+            parse_legacy,
+        ))
+        .collect()
+}
+
+/// the fetch config to use, `to` says what it is needed for in the error when there is none
+pub fn fetch_config(to: &str) -> Result<&'static FetchConfig, AocError> {
+    match Lazy::force(&FETCH_CONFIGS).first() {
+        None => Err(AocError::Config(format!(
+            "fetch config not set, set AOC_SESSION or run set-fetch-config to {to}"
+        ))),
+        Some(Found {
+            config: Ok(config), ..
+        }) => Ok(config),
+        Some(Found {
+            source,
+            config: Err(problems),
+            ..
+        }) => Err(AocError::Config(format!(
+            "the fetch config from {source} is wrong: {}, run check-config for more",
+            problems.join(", ")
+        ))),
+    }
+}
+
+/// the year from the fetch config, if it is set and valid
+pub fn default_year() -> Option<u32> {
+    match Lazy::force(&FETCH_CONFIGS).first() {
+        Some(Found {
+            config: Ok(config), ..
+        }) => config.year,
+        _ => None,
+    }
+}
+
+/// writes config as toml to path, readable only by the user
+pub fn save(path: &Path, config: &FetchConfig) -> Result<(), AocError> {
+    let text = toml::to_string(&ConfigFile {
+        session: config.oauthkey.clone(),
+        agent: config.agent.clone(),
+        year: config.year,
+    })
+    .expect("the config always serializes");
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|err| AocError::io(dir, err))?;
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // mode only applies to new files
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+                .map_err(|err| AocError::io(path, err))?;
+        }
+    }
+
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(text.as_bytes()))
+        .map_err(|err| AocError::io(path, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::HashMap, process};
+
+    const SESSION: &str = "53616c7465645f5f0123456789abcdef0123456789abcdef0123456789abcdef\
+                           0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    fn vars<'a>(pairs: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        let map = pairs.iter().copied().collect::<HashMap<_, _>>();
+        move |name| map.get(name).map(|value| value.to_string())
+    }
+
+    #[test]
+    fn sessions() {
+        assert!(session_problems(SESSION).is_empty());
+        assert!(session_warnings(SESSION).is_empty());
+        assert_eq!(
+            session_problems(&format!("session={SESSION}")),
+            vec!["the session cookie starts with session=, leave that out"]
+        );
+        assert_eq!(session_problems(" "), vec!["the session cookie is empty"]);
+
+        // an odd looking cookie still works, it only warns
+        assert!(session_problems("cafe").is_empty());
+        assert_eq!(
+            session_warnings("cafe"),
+            vec!["the session cookie is 4 characters long, usually it is 128"]
+        );
+        assert!(session_problems(&SESSION.replace('a', "z")).is_empty());
+        assert_eq!(session_warnings(&SESSION.replace('a', "z")).len(), 1);
+    }
+
+    #[test]
+    fn legacy_problems() {
+        let config = parse_legacy(&format!("me\n{SESSION}\n2024\n")).unwrap();
+        assert_eq!(config.year, Some(2024));
+
+        assert_eq!(parse_legacy("me\ncafe").err().unwrap().len(), 1);
+        assert!(parse_legacy("me\ncafe\n2024\n").is_ok());
+        assert_eq!(
+            parse_legacy(&format!("me\n{SESSION}\ntwenty\n"))
+                .err()
+                .unwrap(),
+            vec!["the year on line 3 is \"twenty\", not a number"]
+        );
+    }
+
+    #[test]
+    fn order() {
+        let dir = env::temp_dir().join(format!("aoc-credentials-{}", process::id()));
+        let home = dir.to_str().unwrap();
+        let legacy = dir.join("AOC_FETCH_CONFIG");
+        let xdg = dir.join(".config/aoc/config.toml");
+
+        assert!(find_all(vars(&[("HOME", home)]), &legacy).is_empty());
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&legacy, format!("me\n{SESSION}\n2022\n")).unwrap();
+        let config = FetchConfig {
+            year: Some(2023),
+            agent: "me".to_string(),
+            oauthkey: SESSION.to_string(),
+        };
+        save(&xdg, &config).unwrap();
+
+        let found = find_all(vars(&[("HOME", home), ("AOC_SESSION", SESSION)]), &legacy);
+        let sources = found.iter().map(|f| &f.source).collect::<Vec<_>>();
+        assert_eq!(
+            sources,
+            vec![
+                &Source::Env,
+                &Source::Xdg(xdg.clone()),
+                &Source::Legacy(legacy.clone())
+            ]
+        );
+        assert_eq!(found[1].config.as_ref().unwrap().year, Some(2023));
+        assert!(found[1].warnings.is_empty());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&xdg).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,159 +1,486 @@
+use crate::alloc::{self, AllocStats};
+use crate::answer::Answer;
+use crate::bench::{self, BenchOptions};
+use crate::error::AocError;
+use crate::input::{self, FileSource, InputSource};
+use crate::log::Log;
+use crate::params::{Param, Params};
+use crate::repl;
+use crate::report::{DayReport, Status};
+use crate::select::Parts;
+use crate::unwind;
+use clap::ValueEnum;
 use colored::Colorize;
 use core::fmt;
-use once_cell::sync::Lazy;
 use reqwest::header::USER_AGENT;
-use std::{fs, io::Read, path::Path, time::Instant};
+use std::{
+    fs,
+    io::{self, Read},
+    panic,
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc},
+    thread,
+    time::{Duration, Instant},
+};
 
-pub struct Day<A> {
-    pub parser: Box<dyn Fn(String, bool) -> Result<A, String> + Sync + Send>,
-    pub part_a: Box<dyn Fn(&A) -> Option<String> + Sync + Send>,
-    pub part_b: Box<dyn Fn(&A) -> Option<String> + Sync + Send>,
-    pub exe: Box<dyn Fn(&A) + Sync + Send>,
+pub type PartResult = Result<Answer, AocError>;
+pub type PartFn<S> = fn(&S, &<S as Solution>::Input, &Params, &Log) -> PartResult;
+
+pub fn parts<S: Solution>() -> [(Part, PartFn<S>); 2] {
+    [(Part::A, S::part_a), (Part::B, S::part_b)]
+}
+
+/// the solution to one day's puzzle, see registry::register! for making the runner aware of it
+pub trait Solution: Sync + 'static {
+    type Input: fmt::Debug + Send + Sync;
+
+    fn parse(&self, input: String, log: &Log) -> Result<Self::Input, AocError>;
+
+    fn part_a(&self, input: &Self::Input, params: &Params, log: &Log) -> PartResult;
+
+    fn part_b(&self, input: &Self::Input, params: &Params, log: &Log) -> PartResult;
+
+    /// puzzle constants that can be changed with --param
+    fn params(&self) -> Vec<Param> {
+        Vec::new()
+    }
+
+    /// what the exe subcommand runs, for drawing or dumping the input. args are what came
+    /// after -- on the command line, see utils::visualize_args. None when the day has none
+    fn visualize(
+        &self,
+        _input: &Self::Input,
+        _params: &Params,
+        _args: &[String],
+    ) -> Option<Result<(), AocError>> {
+        None
+    }
+}
+
+/// options from the command line shared by every way of running a day
+#[derive(Clone)]
+pub struct RunOptions {
+    pub year: u32,
+    /// how many times -v was given, see Log
+    pub verbosity: u8,
+    pub params: Vec<(String, i64)>,
+    /// read the input from this file instead of inputs/{year}/day{N}, "-" reads stdin
+    pub input: Option<PathBuf>,
+    /// give up on a part that runs longer than this
+    pub timeout: Option<Duration>,
+    pub parts: Parts,
+    /// where inputs come from when input is not set, see input::default_source
+    pub source: Arc<dyn InputSource>,
+}
+
+pub type DayRunner = &'static dyn RunDay;
+
+/// one of the two puzzles of a day
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Part {
+    A,
+    B,
 }
 
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::A => write!(f, "a"),
+            Part::B => write!(f, "b"),
+        }
+    }
+}
+
+pub const AOC_URL: &str = "https://adventofcode.com";
+
 pub struct FetchConfig {
-    pub year: u64,
+    /// the year used when --year is not given
+    pub year: Option<u32>,
     pub agent: String,
     pub oauthkey: String,
 }
 
-pub static FETCH_CONFIG: Lazy<Option<FetchConfig>> = Lazy::new(|| get_fetch_config());
-
-fn get_fetch_config() -> Option<FetchConfig> {
-    let content = fs::read_to_string("AOC_FETCH_CONFIG").ok()?;
-    let mut content = content.lines();
-    let agent = content.next()?;
-    let oauthkey = content.next()?;
-    let year = content.next()?;
+/// gets url from adventofcode.com as the user in conf
+pub fn fetch(url: &str, conf: &FetchConfig) -> Result<String, AocError> {
+    let res = reqwest::blocking::Client::new()
+        .get(url)
+        .header("Cookie", format!("session={};", conf.oauthkey.trim()))
+        .header(USER_AGENT, &conf.agent)
+        .send()
+        .map_err(|source| AocError::Fetch {
+            url: url.to_string(),
+            source,
+        })?;
 
-    let year = year.parse::<u64>().ok()?;
+    if !res.status().is_success() {
+        return Err(AocError::Http {
+            url: url.to_string(),
+            status: res.status(),
+        });
+    }
 
-    Some(FetchConfig {
-        year,
-        agent: agent.to_string(),
-        oauthkey: oauthkey.to_string(),
+    res.text().map_err(|source| AocError::Fetch {
+        url: url.to_string(),
+        source,
     })
 }
 
-fn get_day_input(day: u32) -> Result<String, String> {
-    let file = format!("inputs/day{day}");
-    let file = Path::new(&file);
+/// the directory inputs are kept (and fetched to) in, as {year}/day{N}
+pub const INPUTS_DIR: &str = "inputs";
 
-    if file.exists() {
-        let mut file = fs::File::open(file).map_err(|err| err.to_string())?;
-        let mut buf = String::new();
-        file.read_to_string(&mut buf)
-            .map_err(|err| err.to_string())?;
-        return Ok(buf);
-    }
+/// where the input of a day is kept (and fetched to)
+pub fn input_path(year: u32, day: u32) -> PathBuf {
+    FileSource::new(INPUTS_DIR).path(year, day)
+}
 
-    match Lazy::force(&FETCH_CONFIG) {
-        Some(conf) => {
-            let client = reqwest::blocking::Client::new();
-            if !Path::new("inputs").exists() {
-                println!(
-                    "please make inputs directory or cd to the path that has the inputs directory"
-                );
-                return Err("no inputs directory".to_string());
-            }
-            println!("fetching day input");
-            let res = client
-                .get(format!(
-                    "https://adventofcode.com/{}/day/{day}/input",
-                    conf.year
-                ))
-                .header("Cookie", format!("session={};", conf.oauthkey))
-                .header(USER_AGENT, &conf.agent)
-                .send()
-                .map_err(|err| err.to_string())?
-                .text()
-                .map_err(|err| err.to_string())?;
-
-            fs::write(file, res.as_bytes()).map_err(|err| err.to_string())?;
-
-            Ok(res)
+/// the directory example inputs are kept in, as {year}/day{N} and {year}/day{N}-{name}
+pub const GOLDENS_DIR: &str = "goldens";
+
+pub fn goldens_dir(year: u32) -> PathBuf {
+    Path::new(GOLDENS_DIR).join(year.to_string())
+}
+
+pub fn read_input(number: u32, options: &RunOptions) -> Result<String, AocError> {
+    match &options.input {
+        Some(path) if path.as_os_str() == "-" => {
+            let mut buf = String::new();
+            io::stdin()
+                .read_to_string(&mut buf)
+                .map_err(|err| AocError::io("<stdin>", err))?;
+            Ok(buf)
         }
-        None => Err(
-            "fetch config not set, set fetch config or add file to inputs directory".to_string(),
-        ),
+        Some(path) => fs::read_to_string(path).map_err(|err| AocError::io(path, err)),
+        None => input::get_day_input(&*options.source, options.year, number),
     }
 }
 
-fn run_day<A>(day: &Day<A>, verbose: bool, number: u32) -> Result<(), String>
-where
-    A: fmt::Debug,
-{
-    println!(
-        "{}",
-        format!("======= Day {:2} ========", number).bright_red()
-    );
+fn run_day<S: Solution>(day: &'static S, options: &RunOptions, number: u32) -> DayReport {
+    let mut report = DayReport::new(options.year, number);
+
+    if let Err(err) = run_parts(day, options, &mut report) {
+        report.error = Some(err);
+    }
 
-    // let file = fs::read_to_string(format!("inputs/day{}", number)).map_err(|e| format!("{e}"))?;
-    let file = get_day_input(number)?;
+    report
+}
 
-    let now = Instant::now();
+fn run_parts<S: Solution>(
+    day: &'static S,
+    options: &RunOptions,
+    report: &mut DayReport,
+) -> Result<(), AocError> {
+    let params = Arc::new(Params::new(&day.params(), &options.params)?);
 
-    let parsed = match (*day.parser)(file, verbose) {
-        Ok(parsed) => parsed,
-        Err(err) => return Err(format!("failed to parse input: {}", err)),
-    };
+    let file = read_input(report.day, options)?;
+    let log = Log::new(report.day, options.verbosity);
 
-    println!("parsing time: {:.2?}", now.elapsed());
+    let ((parsed, elapsed), parse_alloc) =
+        alloc::measure(|| timed(|| unwind::catch(|| day.parse(file, &log))));
 
-    println!("{}", "====== part A ======".bright_magenta());
+    let parsed = Arc::new(parsed??);
 
-    let now = Instant::now();
+    report.parse_time = Some(elapsed);
+    report.parse_alloc = parse_alloc;
 
-    match (*day.part_a)(&parsed) {
-        Some(ret) => {
-            println!("{}", ret);
-            println!("part a time: {:.2?}", now.elapsed());
+    // a part that timed out does not stop the other one, the report keeps the first timeout
+    let mut timed_out = None;
+
+    for (part, (which, solve)) in report.parts.iter_mut().zip(parts::<S>()) {
+        if !options.parts.includes(which) {
+            continue;
+        }
+
+        let solved = match options.timeout {
+            Some(limit) => match solve_with_timeout(day, solve, &parsed, &params, log, limit) {
+                Some(result) => result,
+                None => {
+                    part.status = Status::TimedOut;
+                    timed_out.get_or_insert(AocError::TimedOut { part: which, limit });
+                    continue;
+                }
+            },
+            None => solve_measured(day, solve, &parsed, &params, &log),
+        };
+
+        let answer = match solved.result {
+            Ok(answer) => answer,
+            Err(err) => {
+                part.status = Status::Failed;
+                return Err(err);
+            }
+        };
+
+        part.alloc = solved.alloc;
+        if answer.is_implemented() {
+            part.status = Status::Ok;
+            part.time = Some(solved.time);
+        } else {
+            part.status = Status::NotImplemented;
         }
-        None => println!("not yet implemented"),
+        part.answer = Some(answer);
     }
 
-    println!("{}", "====== part B ======".bright_magenta());
+    match timed_out {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// what running a part gave, with how long it took and what it allocated
+pub struct Solved {
+    pub result: PartResult,
+    pub time: Duration,
+    pub alloc: Option<AllocStats>,
+}
+
+/// a panicking part fails like one that returned an error
+pub fn solve_measured<S: Solution>(
+    day: &S,
+    solve: PartFn<S>,
+    parsed: &S::Input,
+    params: &Params,
+    log: &Log,
+) -> Solved {
+    let ((result, time), alloc) =
+        alloc::measure(|| timed(|| unwind::catch(|| solve(day, parsed, params, log))));
+
+    Solved {
+        result: result.and_then(|res| res),
+        time,
+        alloc,
+    }
+}
 
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
     let now = Instant::now();
+    let res = f();
+    (res, now.elapsed())
+}
 
-    match (*day.part_b)(&parsed) {
-        Some(ret) => {
-            println!("{}", ret);
-            println!("part b time: {:.2?}", now.elapsed());
-        }
-        None => println!("not yet implemented"),
+/// runs the part on its own thread and stops waiting for it after limit, None if it did not finish.
+/// there is no way to stop a thread from the outside, so a part that overran keeps going in the
+/// background until it finishes on its own or the process exits
+fn solve_with_timeout<S: Solution>(
+    day: &'static S,
+    solve: PartFn<S>,
+    parsed: &Arc<S::Input>,
+    params: &Arc<Params>,
+    log: Log,
+    limit: Duration,
+) -> Option<Solved> {
+    let (send, recv) = mpsc::channel();
+    let (parsed, params) = (Arc::clone(parsed), Arc::clone(params));
+
+    let handle = thread::spawn(move || {
+        // the receiver is gone when we already gave up on this part
+        let _ = send.send(solve_measured(day, solve, &parsed, &params, &log));
+    });
+
+    match recv.recv_timeout(limit) {
+        Ok(result) => Some(result),
+        Err(mpsc::RecvTimeoutError::Timeout) => None,
+        // panics in the part are caught on its thread, this is only reached if sending failed
+        Err(mpsc::RecvTimeoutError::Disconnected) => match handle.join() {
+            Err(payload) => panic::resume_unwind(payload),
+            Ok(()) => unreachable!("part thread finished without sending its result"),
+        },
     }
+}
 
-    Ok(())
+fn exe_day<S: Solution>(
+    day: &S,
+    options: &RunOptions,
+    args: &[String],
+    number: u32,
+) -> Result<(), AocError> {
+    let params = Params::new(&day.params(), &options.params)?;
+
+    println!(
+        "{}",
+        format!("======= Day {:2} ========", number).bright_red()
+    );
+
+    let file = read_input(number, options)?;
+
+    let parsed = unwind::catch(|| day.parse(file, &Log::new(number, options.verbosity)))??;
+
+    match unwind::catch(|| day.visualize(&parsed, &params, args))? {
+        Some(res) => res,
+        None => {
+            println!("no visualization for this day");
+            Ok(())
+        }
+    }
 }
 
-fn exe_day<A>(day: &Day<A>, number: u32) -> Result<(), String> {
+fn bench_day<S: Solution>(
+    day: &S,
+    options: &RunOptions,
+    bench: &BenchOptions,
+    number: u32,
+) -> Result<(), AocError> {
+    let params = Params::new(&day.params(), &options.params)?;
+
     println!(
         "{}",
         format!("======= Day {:2} ========", number).bright_red()
     );
 
-    let file = get_day_input(number)?;
+    let file = read_input(number, options)?;
+
+    // logging would end up in the timings
+    let log = Log::quiet();
 
-    let parsed = match (*day.parser)(file, false) {
-        Ok(parsed) => parsed,
-        Err(err) => return Err(format!("failed to parse input: {}", err)),
-    };
+    // parsing and the parts that are run share the budget
+    let phases = 1 + parts::<S>()
+        .iter()
+        .filter(|(which, _)| options.parts.includes(*which))
+        .count() as u32;
+
+    let (parsed, stats) = bench::sample(bench, phases, || day.parse(file.clone(), &log))?;
+    println!("parse  {}", stats);
+
+    for (which, part) in parts::<S>() {
+        if !options.parts.includes(which) {
+            continue;
+        }
 
-    (*day.exe)(&parsed);
+        let (answer, stats) = bench::sample(bench, phases, || part(day, &parsed, &params, &log))?;
+
+        if answer.is_implemented() {
+            println!("part {which} {}", stats);
+        } else {
+            println!("part {which} not yet implemented");
+        }
+    }
 
     Ok(())
 }
 
-pub fn create_day<A>(day: &'static Day<A>) -> Box<dyn Fn(bool, u32, bool) -> Result<(), String>>
-where
-    A: fmt::Debug,
-{
-    Box::new(|verbose, num, exe| {
-        if exe {
-            exe_day(day, num)
-        } else {
-            run_day(day, verbose, num)
+/// the ways of running a day, so days with different parsed input types can sit in one table
+pub trait RunDay: Sync {
+    fn run(&'static self, number: u32, options: &RunOptions) -> DayReport;
+    fn exe(&self, number: u32, options: &RunOptions, args: &[String]) -> Result<(), AocError>;
+    fn repl(&self, number: u32, options: &RunOptions) -> Result<(), AocError>;
+    fn bench(
+        &self,
+        number: u32,
+        options: &RunOptions,
+        bench: &BenchOptions,
+    ) -> Result<(), AocError>;
+}
+
+impl<S: Solution> RunDay for S {
+    fn run(&'static self, number: u32, options: &RunOptions) -> DayReport {
+        run_day(self, options, number)
+    }
+
+    fn exe(&self, number: u32, options: &RunOptions, args: &[String]) -> Result<(), AocError> {
+        exe_day(self, options, args, number)
+    }
+
+    fn repl(&self, number: u32, options: &RunOptions) -> Result<(), AocError> {
+        repl::repl_day(self, options, number)
+    }
+
+    fn bench(
+        &self,
+        number: u32,
+        options: &RunOptions,
+        bench: &BenchOptions,
+    ) -> Result<(), AocError> {
+        bench_day(self, options, bench, number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::MemorySource;
+
+    // part a is instant, part b takes far longer than any test timeout
+    struct Slow;
+
+    impl Solution for Slow {
+        type Input = ();
+
+        fn parse(&self, _input: String, _log: &Log) -> Result<(), AocError> {
+            Ok(())
         }
-    })
+
+        fn part_a(&self, _input: &(), _params: &Params, _log: &Log) -> PartResult {
+            Ok(Answer::Int(1))
+        }
+
+        fn part_b(&self, _input: &(), _params: &Params, _log: &Log) -> PartResult {
+            thread::sleep(Duration::from_secs(10));
+            Ok(Answer::Int(2))
+        }
+    }
+
+    #[test]
+    fn part_times_out() {
+        let options = RunOptions {
+            year: 2024,
+            verbosity: 0,
+            params: Vec::new(),
+            input: Some(PathBuf::from("goldens/2024/day1")),
+            timeout: Some(Duration::from_millis(50)),
+            parts: Parts::Both,
+            source: Arc::new(MemorySource::default()),
+        };
+
+        let now = Instant::now();
+        let report = Slow.run(1, &options);
+
+        assert!(now.elapsed() < Duration::from_secs(5));
+        assert_eq!(report.parts[0].status, Status::Ok);
+        assert_eq!(report.parts[1].status, Status::TimedOut);
+        assert!(matches!(
+            report.error,
+            Some(AocError::TimedOut { part: Part::B, .. })
+        ));
+    }
+
+    struct SlowA;
+
+    impl Solution for SlowA {
+        type Input = ();
+
+        fn parse(&self, _input: String, _log: &Log) -> Result<(), AocError> {
+            Ok(())
+        }
+
+        fn part_a(&self, _input: &(), _params: &Params, _log: &Log) -> PartResult {
+            thread::sleep(Duration::from_secs(10));
+            Ok(Answer::Int(1))
+        }
+
+        fn part_b(&self, _input: &(), _params: &Params, _log: &Log) -> PartResult {
+            Ok(Answer::Int(2))
+        }
+    }
+
+    #[test]
+    fn part_b_runs_after_a_times_out() {
+        let options = RunOptions {
+            year: 2024,
+            verbosity: 0,
+            params: Vec::new(),
+            input: None,
+            timeout: Some(Duration::from_millis(50)),
+            parts: Parts::Both,
+            source: Arc::new(MemorySource::default().with(2024, 1, "")),
+        };
+
+        let report = SlowA.run(1, &options);
+
+        assert_eq!(report.parts[0].status, Status::TimedOut);
+        assert_eq!(report.parts[1].status, Status::Ok);
+        assert_eq!(report.parts[1].answer, Some(Answer::Int(2)));
+        assert!(matches!(
+            report.error,
+            Some(AocError::TimedOut { part: Part::A, .. })
+        ));
+    }
 }
@@ -1,38 +1,15 @@
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
+use crate::utils;
 use itertools::Itertools;
-use once_cell::sync::Lazy;
 use std::collections::HashSet;
 
 type Input = Vec<Vec<u8>>;
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    Ok(input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|d| d.to_digit(10).unwrap() as u8)
-                .collect_vec()
-        })
-        .collect_vec())
-}
-
-fn part_a(input: &Input) -> Option<String> {
-    Some(
-        input
-            .iter()
-            .enumerate()
-            .map(|(y, row)| {
-                row.iter()
-                    .enumerate()
-                    .filter(|(_x, c)| **c == 0)
-                    .map(|(x, _c)| find_score(input, x, y))
-                    .sum::<u32>()
-            })
-            .sum::<u32>()
-            .to_string(),
-    )
-}
-
 fn find_score(input: &[Vec<u8>], x: usize, y: usize) -> u32 {
     find_score_go(input, Box::new([(x as i32, y as i32)].into_iter()), 1)
 }
@@ -83,23 +60,6 @@ fn find_score_go<'a>(
     find_score_go(input, Box::new(nlocs), next + 1)
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    Some(
-        input
-            .iter()
-            .enumerate()
-            .map(|(y, row)| {
-                row.iter()
-                    .enumerate()
-                    .filter(|(_x, c)| **c == 0)
-                    .map(|(x, _c)| find_score_b(input, x, y))
-                    .sum::<u32>()
-            })
-            .sum::<u32>()
-            .to_string(),
-    )
-}
-
 fn find_score_b(input: &[Vec<u8>], x: usize, y: usize) -> u32 {
     find_score_go_b(input, Box::new([(x as i32, y as i32)].into_iter()), 1)
 }
@@ -142,17 +102,49 @@ fn find_score_go_b<'a>(
     find_score_go_b(input, Box::new(nlocs), next + 1)
 }
 
-fn exe(_input: &Input) {
-    todo!()
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        utils::grid(utils::lines(&input), |d| d.to_digit(10).map(|d| d as u8))
+    }
+
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        Ok(Answer::from(
+            input
+                .iter()
+                .enumerate()
+                .map(|(y, row)| {
+                    row.iter()
+                        .enumerate()
+                        .filter(|(_x, c)| **c == 0)
+                        .map(|(x, _c)| find_score(input, x, y))
+                        .sum::<u32>()
+                })
+                .sum::<u32>(),
+        ))
+    }
+
+    fn part_b(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        Ok(Answer::from(
+            input
+                .iter()
+                .enumerate()
+                .map(|(y, row)| {
+                    row.iter()
+                        .enumerate()
+                        .filter(|(_x, c)| **c == 0)
+                        .map(|(x, _c)| find_score_b(input, x, y))
+                        .sum::<u32>()
+                })
+                .sum::<u32>(),
+        ))
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 10, Day10);
 
 #[cfg(test)]
 mod tests {
@@ -161,11 +153,17 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day10", &DAY, Some("36"), Some("81"), false)
+        utils::golden(
+            "2024/day10",
+            &Day10,
+            Some(Answer::Int(36)),
+            Some(Answer::Int(81)),
+            false,
+        )
     }
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(10, &DAY, Some("776"), Some("1657"), false);
+        utils::finalanswer(2024, 10, &Day10, false);
     }
 }
@@ -1,32 +1,14 @@
-use crate::day;
-use itertools::Itertools;
-use once_cell::sync::Lazy;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::{Param, Params};
+use crate::registry;
+use crate::utils;
 use std::collections::HashMap;
 
 type Input = Vec<u64>;
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    Ok(input
-        .lines()
-        .next()
-        .unwrap()
-        .split(' ')
-        .map(|num| num.parse::<u64>().unwrap())
-        .collect_vec())
-}
-
-fn part_a(input: &Input) -> Option<String> {
-    let mut cache = HashMap::new();
-
-    Some(
-        input
-            .iter()
-            .map(|num| count_final_cached(*num, 25, &mut cache))
-            .sum::<u64>()
-            .to_string(),
-    )
-}
-
 fn digits(b: u64) -> u32 {
     b.ilog(10) + 1
 }
@@ -59,29 +41,65 @@ fn count_final_cached(num: u64, times: u32, cache: &mut HashMap<(u64, u32), u64>
     res
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    let mut cache = HashMap::new();
+pub struct Day11;
 
-    Some(
-        input
-            .iter()
-            .map(|num| count_final_cached(*num, 75, &mut cache))
-            .sum::<u64>()
-            .to_string(),
-    )
-}
+impl Solution for Day11 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        let line = utils::lines(&input)
+            .next()
+            .ok_or_else(|| AocError::parse(1, 1, "", "empty input"))?;
+
+        line.text
+            .split(' ')
+            .map(|num| line.num::<u64>(num))
+            .collect()
+    }
+
+    fn part_a(&self, input: &Input, params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let mut cache = HashMap::new();
+        let blinks = params.get_as::<u32>("blinks_a")?;
+
+        Ok(Answer::from(
+            input
+                .iter()
+                .map(|num| count_final_cached(*num, blinks, &mut cache))
+                .sum::<u64>(),
+        ))
+    }
 
-fn exe(_input: &Input) {
-    todo!()
+    fn part_b(&self, input: &Input, params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let mut cache = HashMap::new();
+        let blinks = params.get_as::<u32>("blinks_b")?;
+
+        Ok(Answer::from(
+            input
+                .iter()
+                .map(|num| count_final_cached(*num, blinks, &mut cache))
+                .sum::<u64>(),
+        ))
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param {
+                name: "blinks_a",
+                default: 25,
+                min: 0,
+                max: 90,
+            },
+            Param {
+                name: "blinks_b",
+                default: 75,
+                min: 0,
+                max: 90,
+            },
+        ]
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 11, Day11);
 
 #[cfg(test)]
 mod tests {
@@ -90,7 +108,13 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day11", &DAY, Some("55312"), Some("65601038650482"), false)
+        utils::golden(
+            "2024/day11",
+            &Day11,
+            Some(Answer::Int(55312)),
+            Some(Answer::Int(65601038650482)),
+            false,
+        )
     }
 
     #[test]
@@ -100,6 +124,6 @@ mod tests {
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(11, &DAY, Some("197357"), Some("234568186890978"), false);
+        utils::finalanswer(2024, 11, &Day11, false);
     }
 }
@@ -1,38 +1,15 @@
 use bitvec::{bitvec, vec::BitVec};
 use itertools::Itertools;
-use once_cell::sync::Lazy;
 
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
 
 type Input = Vec<Vec<char>>;
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    Ok(input
-        .lines()
-        .map(|line| line.chars().collect_vec())
-        .collect_vec())
-}
-
-fn part_a(input: &Input) -> Option<String> {
-    let mut visited = vec![bitvec![0; input[0].len()]; input.len()];
-
-    let mut sum = 0;
-
-    for x in 0..input[0].len() {
-        for y in 0..input.len() {
-            if visited[y][x] {
-                continue;
-            }
-
-            let (a, p) = score(input, x, y, &mut visited);
-
-            sum += a * p;
-        }
-    }
-
-    Some(sum.to_string())
-}
-
 fn score(input: &[Vec<char>], x: usize, y: usize, visited: &mut [BitVec]) -> (u32, u32) {
     if visited[y][x] {
         return (0, 0);
@@ -72,26 +49,6 @@ fn score(input: &[Vec<char>], x: usize, y: usize, visited: &mut [BitVec]) -> (u3
     (area, perimiter - connections)
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    let mut visited = vec![bitvec![0; input[0].len()]; input.len()];
-
-    let mut sum = 0;
-
-    for x in 0..input[0].len() {
-        for y in 0..input.len() {
-            if visited[y][x] {
-                continue;
-            }
-
-            let (a, p) = score2(input, x, y, &mut visited);
-
-            sum += a * p;
-        }
-    }
-
-    Some(sum.to_string())
-}
-
 fn score2(input: &[Vec<char>], x: usize, y: usize, visited: &mut [BitVec]) -> (u32, u32) {
     if visited[y][x] {
         return (0, 0);
@@ -167,17 +124,60 @@ fn score2(input: &[Vec<char>], x: usize, y: usize, visited: &mut [BitVec]) -> (u
     (area, perimiter)
 }
 
-fn exe(_input: &Input) {
-    todo!()
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        Ok(input
+            .lines()
+            .map(|line| line.chars().collect_vec())
+            .collect_vec())
+    }
+
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let mut visited = vec![bitvec![0; input[0].len()]; input.len()];
+
+        let mut sum = 0;
+
+        for x in 0..input[0].len() {
+            for y in 0..input.len() {
+                if visited[y][x] {
+                    continue;
+                }
+
+                let (a, p) = score(input, x, y, &mut visited);
+
+                sum += a * p;
+            }
+        }
+
+        Ok(sum.into())
+    }
+
+    fn part_b(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let mut visited = vec![bitvec![0; input[0].len()]; input.len()];
+
+        let mut sum = 0;
+
+        for x in 0..input[0].len() {
+            for y in 0..input.len() {
+                if visited[y][x] {
+                    continue;
+                }
+
+                let (a, p) = score2(input, x, y, &mut visited);
+
+                sum += a * p;
+            }
+        }
+
+        Ok(sum.into())
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 12, Day12);
 
 #[cfg(test)]
 mod tests {
@@ -186,12 +186,24 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day12-1", &DAY, Some("140"), Some("80"), false);
-        utils::golden("day12-2", &DAY, Some("1930"), Some("1206"), false);
+        utils::golden(
+            "2024/day12-1",
+            &Day12,
+            Some(Answer::Int(140)),
+            Some(Answer::Int(80)),
+            false,
+        );
+        utils::golden(
+            "2024/day12-2",
+            &Day12,
+            Some(Answer::Int(1930)),
+            Some(Answer::Int(1206)),
+            false,
+        );
     }
 
-       #[test]
-       fn finalanswer() {
-           utils::finalanswer(12, &DAY, Some("1465968"), Some("897702"), false);
-       }
+    #[test]
+    fn finalanswer() {
+        utils::finalanswer(2024, 12, &Day12, false);
+    }
 }
@@ -1,34 +1,22 @@
 use itertools::Itertools;
 use num::rational::Ratio;
-use once_cell::sync::Lazy;
 use std::mem;
 
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
+use crate::utils;
 
 type Input = Vec<((i64, i64), (i64, i64), (i64, i64))>;
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    Ok(input
-        .split("\n\n")
-        .map(|section| {
-            let mut lines = section.lines();
-            let (ax, ay) = lines.next().unwrap()[12..].split_once(", Y+").unwrap();
-            let ax = ax.parse::<i64>().unwrap();
-            let ay = ay.parse::<i64>().unwrap();
-            let (bx, by) = lines.next().unwrap()[12..].split_once(", Y+").unwrap();
-            let bx = bx.parse::<i64>().unwrap();
-            let by = by.parse::<i64>().unwrap();
-            let (px, py) = &lines.next().unwrap()[9..].split_once(", Y=").unwrap();
-            let px = px.parse::<i64>().unwrap();
-            let py = py.parse::<i64>().unwrap();
-            ((ax, ay), (bx, by), (px, py))
-        })
-        .collect_vec())
-}
-
+/// the fewest tokens to reach the prize, None when it can not be reached. machines where
+/// both buttons move the claw in the same direction have no single solution and are not solved
 fn optimal_solution(
     ((ax, ay), (bx, by), (px, py)): ((i64, i64), (i64, i64), (i64, i64)),
-) -> Option<i64> {
+) -> Result<Option<i64>, AocError> {
     let mut ax = Ratio::new_raw(ax, 1);
     let mut ay = Ratio::new_raw(ay, 1);
     let mut bx = Ratio::new_raw(bx, 1);
@@ -43,14 +31,21 @@ fn optimal_solution(
     }
 
     if ax == 0.into() {
-        if px != 0.into() {
-            return None;
-        }
-        todo!("even");
+        // button A does nothing, only B can get the claw there
+        let presses = if bx != 0.into() {
+            px / bx
+        } else if by != 0.into() {
+            py / by
+        } else {
+            0.into()
+        };
+        let reaches =
+            presses.is_integer() && presses >= 0.into() && bx * presses == px && by * presses == py;
+        return Ok(reaches.then(|| presses.to_integer()));
     }
 
     if ay == 0.into() {
-        return None;
+        return Ok(None);
     }
 
     // add multiple of x row to y row
@@ -58,11 +53,13 @@ fn optimal_solution(
     py -= ax.recip() * ay * px;
 
     if by == 0.into() && py != 0.into() {
-        return None;
+        return Ok(None);
     }
 
     if by == 0.into() && py == 0.into() {
-        todo!("meow")
+        return Err(AocError::Solve(
+            "buttons A and B move the claw in the same direction".to_string(),
+        ));
     }
 
     // multiply bottom row by recip of by to make by spot one
@@ -70,7 +67,7 @@ fn optimal_solution(
     py = py * by.recip();
 
     if *py.denom() != 1 {
-        return None;
+        return Ok(None);
     }
 
     px -= py * bx;
@@ -78,45 +75,62 @@ fn optimal_solution(
     px *= ax.recip();
 
     if *px.denom() != 1 {
-        return None;
+        return Ok(None);
     }
 
-    Some(3 * *px.numer() + *py.numer())
-}
-
-fn part_a(input: &Input) -> Option<String> {
-    Some(
-        input
-            .iter()
-            .map(|a| optimal_solution(*a).unwrap_or(0))
-            .sum::<i64>()
-            .to_string(),
-    )
+    Ok(Some(3 * *px.numer() + *py.numer()))
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    Some(
-        input
-            .iter()
-            .map(|(a, b, (px, py))| {
-                optimal_solution((*a, *b, (px + 10000000000000, py + 10000000000000))).unwrap_or(0)
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        let lines = utils::lines(&input)
+            .filter(|line| !line.text.is_empty())
+            .collect_vec();
+
+        lines
+            .chunks(3)
+            .map(|section| {
+                let pair = |n: usize, prefix: &str, sep: &str| {
+                    let line = section.get(n).ok_or_else(|| {
+                        let last = section[section.len() - 1];
+                        AocError::parse(last.row + 2, 1, "", format!("expected {:?}", prefix))
+                    })?;
+                    let (x, y) = line.split(line.strip(line.text, prefix)?, sep)?;
+                    Ok::<_, AocError>((line.num::<i64>(x)?, line.num::<i64>(y)?))
+                };
+
+                Ok((
+                    pair(0, "Button A: X+", ", Y+")?,
+                    pair(1, "Button B: X+", ", Y+")?,
+                    pair(2, "Prize: X=", ", Y=")?,
+                ))
             })
-            .sum::<i64>()
-            .to_string(),
-    )
-}
+            .collect()
+    }
 
-fn exe(_input: &Input) {
-    todo!()
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let mut total = 0;
+        for machine in input {
+            total += optimal_solution(*machine)?.unwrap_or(0);
+        }
+        Ok(Answer::from(total))
+    }
+
+    fn part_b(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let mut total = 0;
+        for (a, b, (px, py)) in input {
+            total += optimal_solution((*a, *b, (px + 10000000000000, py + 10000000000000)))?
+                .unwrap_or(0);
+        }
+        Ok(Answer::from(total))
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 13, Day13);
 
 #[cfg(test)]
 mod tests {
@@ -125,11 +139,28 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day13", &DAY, Some("480"), Some("875318608908"), false);
+        utils::golden(
+            "2024/day13",
+            &Day13,
+            Some(Answer::Int(480)),
+            Some(Answer::Int(875318608908)),
+            false,
+        );
+    }
+
+    #[test]
+    fn degenerate_machines() {
+        assert_eq!(optimal_solution(((0, 0), (2, 3), (4, 6))).unwrap(), Some(2));
+        assert_eq!(optimal_solution(((0, 0), (2, 3), (4, 5))).unwrap(), None);
+        assert!(matches!(
+            optimal_solution(((1, 2), (2, 4), (3, 6))),
+            Err(AocError::Solve(_))
+        ));
+        assert_eq!(optimal_solution(((1, 2), (2, 4), (3, 5))).unwrap(), None);
     }
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(13, &DAY, Some("32067"), Some("92871736253789"), false);
+        utils::finalanswer(2024, 13, &Day13, false);
     }
 }
@@ -1,27 +1,19 @@
-use crate::day;
-use image::Rgb;
-use itertools::Itertools;
-use once_cell::sync::Lazy;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::{Param, Params};
+use crate::registry;
+use crate::utils;
+use clap::Parser;
+use image::{ImageError, ImageFormat, Rgb};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
 
 type Input = Vec<(i32, i32, i32, i32)>;
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    Ok(input
-        .lines()
-        .map(|line| {
-            let (pos, vel) = line[2..].split_once(" v=").unwrap();
-            let (x, y) = pos.split_once(',').unwrap();
-            let (vx, vy) = vel.split_once(',').unwrap();
-            (
-                x.parse::<i32>().unwrap(),
-                y.parse::<i32>().unwrap(),
-                vx.parse::<i32>().unwrap(),
-                vy.parse::<i32>().unwrap(),
-            )
-        })
-        .collect_vec())
-}
-
 fn solve_steps(input: &Input, sx: i32, sy: i32, steps: i32) -> (u32, u32, u32, u32) {
     let stepsy = steps.rem_euclid(sy);
     let stepsx = steps.rem_euclid(sx);
@@ -58,13 +50,34 @@ fn solve_steps(input: &Input, sx: i32, sy: i32, steps: i32) -> (u32, u32, u32, u
         })
 }
 
-fn part_a(input: &Input) -> Option<String> {
-    let (a, b, c, d) = solve_steps(input, 101, 103, 100);
+/// what exe --day 14 draws, every step from --from on is written to <out>/<step>.<format>
+#[derive(Parser)]
+struct Draw {
+    /// directory to write the images to, created if missing
+    #[arg(long, default_value = "day14")]
+    out: PathBuf,
 
-    Some((a * b * c * d).to_string())
+    /// first step to draw
+    #[arg(long, default_value_t = 1)]
+    from: i32,
+
+    /// how many steps to draw
+    #[arg(long, default_value_t = 10000)]
+    steps: i32,
+
+    /// image format, by file extension
+    #[arg(long, default_value = "png")]
+    format: String,
 }
 
-fn run_steps(input: &Input, sx: i32, sy: i32, steps: i32) {
+fn run_steps(
+    input: &Input,
+    sx: i32,
+    sy: i32,
+    steps: i32,
+    path: &Path,
+    format: ImageFormat,
+) -> Result<(), AocError> {
     let stepsy = steps.rem_euclid(sy);
     let stepsx = steps.rem_euclid(sx);
 
@@ -79,56 +92,132 @@ fn run_steps(input: &Input, sx: i32, sy: i32, steps: i32) {
         img.put_pixel(fx as u32, fy as u32, Rgb([255u8, 255, 255]));
     }
 
-    img.save(format!("/home/a/day14result/{steps}.png")).unwrap();
+    img.save_with_format(path, format).map_err(|err| match err {
+        ImageError::IoError(err) => AocError::io(path, err),
+        err => AocError::io(path, io::Error::other(err)),
+    })
 }
 
-fn part_b(_input: &Input) -> Option<String> {
-    None
-}
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        utils::lines(&input)
+            .map(|line| {
+                let (pos, vel) = line.split(line.strip(line.text, "p=")?, " v=")?;
+                let (x, y) = line.split(pos, ",")?;
+                let (vx, vy) = line.split(vel, ",")?;
+                Ok((
+                    line.num::<i32>(x)?,
+                    line.num::<i32>(y)?,
+                    line.num::<i32>(vx)?,
+                    line.num::<i32>(vy)?,
+                ))
+            })
+            .collect()
+    }
 
-fn exe(input: &Input) {
-    let mut i = 1;
+    fn part_a(&self, input: &Input, params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let (a, b, c, d) = solve_steps(
+            input,
+            params.get_as::<i32>("width")?,
+            params.get_as::<i32>("height")?,
+            params.get_as::<i32>("steps")?,
+        );
 
-    loop {
-        println!("i = {i}");
-        run_steps(input, 101, 103, i);
-        i += 1;
+        Ok((a * b * c * d).into())
+    }
 
-        if i > 10000 {
-            break;
-        }
+    fn part_b(&self, _input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        Ok(Answer::NotImplemented)
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param {
+                name: "width",
+                default: 101,
+                min: 1,
+                max: 10000,
+            },
+            Param {
+                name: "height",
+                default: 103,
+                min: 1,
+                max: 10000,
+            },
+            Param {
+                name: "steps",
+                default: 100,
+                min: 0,
+                max: i32::MAX.into(),
+            },
+        ]
+    }
+
+    fn visualize(
+        &self,
+        input: &Input,
+        params: &Params,
+        args: &[String],
+    ) -> Option<Result<(), AocError>> {
+        Some(draw(input, params, args))
     }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+fn draw(input: &Input, params: &Params, args: &[String]) -> Result<(), AocError> {
+    let Some(draw) = utils::visualize_args::<Draw>(14, args)? else {
+        return Ok(());
+    };
+
+    let format = ImageFormat::from_extension(&draw.format)
+        .ok_or_else(|| AocError::Config(format!("{:?} is not an image format", draw.format)))?;
+
+    fs::create_dir_all(&draw.out).map_err(|err| AocError::io(&draw.out, err))?;
+
+    for step in draw.from..draw.from + draw.steps {
+        run_steps(
+            input,
+            params.get_as::<i32>("width")?,
+            params.get_as::<i32>("height")?,
+            step,
+            &draw.out.join(format!("{step}.{}", draw.format)),
+            format,
+        )?;
+    }
+
+    println!(
+        "drew steps {} to {} in {}",
+        draw.from,
+        draw.from + draw.steps - 1,
+        draw.out.display()
+    );
+    Ok(())
+}
+
+registry::register!(2024, 14, Day14);
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::set_function;
+    use crate::utils;
 
     #[test]
     fn goldens() {
-        set_function(
-            "day14",
-            &parser,
-            &|input: &Input| {
-                let (a, b, c, d) = solve_steps(input, 11, 7, 100);
-                (a * b * c * d).to_string()
-            },
-            "12",
+        utils::golden_params(
+            "2024/day14",
+            &Day14,
+            &[("width", 11), ("height", 7)],
+            Some(Answer::Int(12)),
+            None,
             false,
         );
     }
 
     // #[test]
     // fn finalanswer() {
-    //     utils::finalanswer(1, &DAY, Some("2057374"), Some("23177084"), false);
+    //     utils::finalanswer(2024, 14, &Day14, false);
     // }
 }
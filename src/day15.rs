@@ -1,6 +1,11 @@
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
+use crate::utils;
 use itertools::Itertools;
-use once_cell::sync::Lazy;
 
 #[derive(Debug, PartialOrd, PartialEq, Eq, Ord, Clone, Copy)]
 pub enum Loc {
@@ -20,68 +25,6 @@ pub enum Instr {
 
 type Input = (Vec<Vec<Loc>>, Vec<Instr>);
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    let (field, instrs) = input.split_once("\n\n").unwrap();
-
-    let instrs = instrs
-        .lines()
-        .flat_map(|line| {
-            line.chars().map(|c| match c {
-                '^' => Instr::Up,
-                '<' => Instr::Left,
-                '>' => Instr::Right,
-                'v' => Instr::Down,
-                _ => panic!("invalid input"),
-            })
-        })
-        .collect_vec();
-
-    let field = field
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '#' => Loc::Wall,
-                    'O' => Loc::Box,
-                    '@' => Loc::Bot,
-                    '.' => Loc::Empty,
-                    _ => panic!("invalid input"),
-                })
-                .collect_vec()
-        })
-        .collect_vec();
-
-    Ok((field, instrs))
-}
-
-fn part_a(input: &Input) -> Option<String> {
-    let mut field = input.0.clone();
-
-    let robot_loc = field
-        .iter_mut()
-        .enumerate()
-        .flat_map(|(row, line)| {
-            line.iter_mut()
-                .enumerate()
-                .filter_map(|(c, l): (usize, &mut Loc)| {
-                    let res = *l == Loc::Bot;
-                    if res {
-                        *l = Loc::Empty;
-                        Some((c as i32, row as i32))
-                    } else {
-                        None
-                    }
-                })
-                .next()
-        })
-        .next()
-        .unwrap();
-
-    simulate(robot_loc, &input.1, &mut field);
-
-    Some(score_field(field).to_string())
-}
-
 fn score_field(field: Vec<Vec<Loc>>) -> u32 {
     field
         .into_iter()
@@ -150,62 +93,6 @@ enum NLoc {
     Empty,
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    let mut field = input.0.clone();
-
-    let robot_loc = field
-        .iter_mut()
-        .enumerate()
-        .flat_map(|(row, line)| {
-            line.iter_mut()
-                .enumerate()
-                .filter_map(|(c, l): (usize, &mut Loc)| {
-                    let res = *l == Loc::Bot;
-                    if res {
-                        *l = Loc::Empty;
-                        Some((c as i32 * 2, row as i32))
-                    } else {
-                        None
-                    }
-                })
-                .next()
-        })
-        .next()
-        .unwrap();
-
-    let mut field = field
-        .into_iter()
-        .map(|row| {
-            row.into_iter()
-                .flat_map(|loc| match loc {
-                    Loc::Wall => [NLoc::Wall, NLoc::Wall],
-                    Loc::Box => [NLoc::BoxL, NLoc::BoxR],
-                    Loc::Bot => panic!("should have been removed"),
-                    Loc::Empty => [NLoc::Empty, NLoc::Empty],
-                })
-                .collect_vec()
-        })
-        .collect_vec();
-
-    simulate2(robot_loc, &input.1, &mut field);
-
-    // for line in field.iter() {
-    //     println!(
-    //         "{}",
-    //         line.iter()
-    //             .map(|l| match l {
-    //                 NLoc::Wall => '#',
-    //                 NLoc::BoxL => '[',
-    //                 NLoc::BoxR => ']',
-    //                 NLoc::Empty => '.',
-    //             })
-    //             .collect::<String>()
-    //     );
-    // }
-
-    Some(score_field2(field).to_string())
-}
-
 fn score_field2(field: Vec<Vec<NLoc>>) -> u32 {
     field
         .into_iter()
@@ -332,17 +219,125 @@ fn can_push(nx: i32, ny: i32, dir: (i32, i32), field: &[Vec<NLoc>]) -> bool {
     }
 }
 
-fn exe(_input: &Input) {
-    todo!()
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        let mut lines = utils::lines(&input);
+
+        let field = utils::grid(
+            lines.by_ref().take_while(|line| !line.text.is_empty()),
+            |c| match c {
+                '#' => Some(Loc::Wall),
+                'O' => Some(Loc::Box),
+                '@' => Some(Loc::Bot),
+                '.' => Some(Loc::Empty),
+                _ => None,
+            },
+        )?;
+
+        let instrs = utils::grid(lines, |c| match c {
+            '^' => Some(Instr::Up),
+            '<' => Some(Instr::Left),
+            '>' => Some(Instr::Right),
+            'v' => Some(Instr::Down),
+            _ => None,
+        })?
+        .concat();
+
+        Ok((field, instrs))
+    }
+
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let mut field = input.0.clone();
+
+        let robot_loc = field
+            .iter_mut()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                line.iter_mut()
+                    .enumerate()
+                    .filter_map(|(c, l): (usize, &mut Loc)| {
+                        let res = *l == Loc::Bot;
+                        if res {
+                            *l = Loc::Empty;
+                            Some((c as i32, row as i32))
+                        } else {
+                            None
+                        }
+                    })
+                    .next()
+            })
+            .next()
+            .unwrap();
+
+        simulate(robot_loc, &input.1, &mut field);
+
+        Ok(score_field(field).into())
+    }
+
+    fn part_b(&self, input: &Input, _params: &Params, log: &Log) -> Result<Answer, AocError> {
+        let mut field = input.0.clone();
+
+        let robot_loc = field
+            .iter_mut()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                line.iter_mut()
+                    .enumerate()
+                    .filter_map(|(c, l): (usize, &mut Loc)| {
+                        let res = *l == Loc::Bot;
+                        if res {
+                            *l = Loc::Empty;
+                            Some((c as i32 * 2, row as i32))
+                        } else {
+                            None
+                        }
+                    })
+                    .next()
+            })
+            .next()
+            .unwrap();
+
+        let mut field = field
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .flat_map(|loc| match loc {
+                        Loc::Wall => [NLoc::Wall, NLoc::Wall],
+                        Loc::Box => [NLoc::BoxL, NLoc::BoxR],
+                        Loc::Bot => panic!("should have been removed"),
+                        Loc::Empty => [NLoc::Empty, NLoc::Empty],
+                    })
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        simulate2(robot_loc, &input.1, &mut field);
+
+        log.debug(|| {
+            field
+                .iter()
+                .map(|line| {
+                    line.iter()
+                        .map(|l| match l {
+                            NLoc::Wall => '#',
+                            NLoc::BoxL => '[',
+                            NLoc::BoxR => ']',
+                            NLoc::Empty => '.',
+                        })
+                        .collect::<String>()
+                })
+                .join("\n")
+        });
+
+        Ok(score_field2(field).into())
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 15, Day15);
 
 #[cfg(test)]
 mod tests {
@@ -351,13 +346,19 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day15-s", &DAY, Some("2028"), None, false);
-        utils::golden("day15-e", &DAY, None, Some("618"), false);
-        utils::golden("day15-l", &DAY, Some("10092"), Some("9021"), false)
+        utils::golden("2024/day15-s", &Day15, Some(Answer::Int(2028)), None, false);
+        utils::golden("2024/day15-e", &Day15, None, Some(Answer::Int(618)), false);
+        utils::golden(
+            "2024/day15-l",
+            &Day15,
+            Some(Answer::Int(10092)),
+            Some(Answer::Int(9021)),
+            false,
+        )
     }
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(15, &DAY, Some("1349898"), Some("1376686"), false);
+        utils::finalanswer(2024, 15, &Day15, false);
     }
 }
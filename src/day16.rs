@@ -1,8 +1,12 @@
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
+use crate::utils;
 use bitvec::bitvec;
 use bitvec::vec::BitVec;
-use itertools::Itertools;
-use once_cell::sync::Lazy;
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 
@@ -14,59 +18,48 @@ pub enum Loc {
     Wall,
 }
 
-type Input = Vec<Vec<Loc>>;
-
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    Ok(input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '.' => Loc::Empty,
-                    '#' => Loc::Wall,
-                    'S' => Loc::Start,
-                    'E' => Loc::End,
-                    _ => panic!("invalid input"),
-                })
-                .collect_vec()
-        })
-        .collect_vec())
+type Maze = Vec<Vec<Loc>>;
+
+#[derive(Debug)]
+pub struct Input {
+    maze: Maze,
+    start: (i32, i32),
+    end: (i32, i32),
 }
 
-fn part_a(input: &Input) -> Option<String> {
-    let start_loc = input
-        .iter()
-        .enumerate()
-        .filter_map(|(row, line)| {
-            line.iter()
-                .enumerate()
-                .filter_map(|(col, loc)| match loc {
-                    Loc::Start => Some((col as i32, row as i32)),
-                    _ => None,
-                })
-                .next()
-        })
-        .next()
-        .unwrap();
+// where the only tile of a kind is, as (x, y)
+fn find_one(maze: &Maze, wanted: Loc, name: char) -> Result<(i32, i32), AocError> {
+    let mut found = maze.iter().enumerate().flat_map(|(row, line)| {
+        line.iter()
+            .enumerate()
+            .filter(|(_, loc)| **loc == wanted)
+            .map(move |(col, _)| (col as i32, row as i32))
+    });
 
-    let end_loc = input
-        .iter()
-        .enumerate()
-        .filter_map(|(row, line)| {
-            line.iter()
-                .enumerate()
-                .filter_map(|(col, loc)| match loc {
-                    Loc::End => Some((col as i32, row as i32)),
-                    _ => None,
-                })
-                .next()
-        })
+    let first = found
         .next()
-        .unwrap();
+        .ok_or_else(|| AocError::parse(1, 1, "", format!("no {name:?} in the maze")))?;
+    if let Some((col, row)) = found.next() {
+        return Err(AocError::parse(
+            row as usize + 1,
+            col as usize + 1,
+            name.to_string(),
+            format!("more than one {name:?} in the maze"),
+        ));
+    }
+    Ok(first)
+}
 
-    let distance = pathfind(start_loc, end_loc, input);
+fn open(input: &Maze, (x, y): (i32, i32)) -> bool {
+    let loc = usize::try_from(y)
+        .ok()
+        .zip(usize::try_from(x).ok())
+        .and_then(|(y, x)| input.get(y)?.get(x));
+    loc.is_some_and(|loc| *loc != Loc::Wall)
+}
 
-    Some(distance.to_string())
+fn no_path() -> AocError {
+    AocError::Solve("no path through the maze".to_string())
 }
 
 fn dirtoind(dir: (i32, i32)) -> usize {
@@ -74,7 +67,7 @@ fn dirtoind(dir: (i32, i32)) -> usize {
     // ((dir.0 + 1) / 2 + (dir.1 + 5) / 2) as usize
 }
 
-fn pathfind(start_loc: (i32, i32), end_loc: (i32, i32), input: &Input) -> u32 {
+fn pathfind(start_loc: (i32, i32), end_loc: (i32, i32), input: &Maze) -> Result<u32, AocError> {
     let mut current_lowest = vec![vec![vec![u32::MAX; 4]; input[0].len()]; input.len()];
     let mut queue: BinaryHeap<(Reverse<u32>, (i32, i32), (i32, i32))> = BinaryHeap::new();
 
@@ -84,7 +77,7 @@ fn pathfind(start_loc: (i32, i32), end_loc: (i32, i32), input: &Input) -> u32 {
 
     while let Some((_, facing, loc)) = queue.pop() {
         if loc == end_loc {
-            return current_lowest[end_loc.1 as usize][end_loc.0 as usize][dirtoind(facing)];
+            return Ok(current_lowest[end_loc.1 as usize][end_loc.0 as usize][dirtoind(facing)]);
         }
 
         let d = current_lowest[loc.1 as usize][loc.0 as usize][dirtoind(facing)];
@@ -92,7 +85,7 @@ fn pathfind(start_loc: (i32, i32), end_loc: (i32, i32), input: &Input) -> u32 {
         for (ndir, nloc) in [(-1, 0), (1, 0), (0, 1), (0, -1)]
             .into_iter()
             .map(|(dx, dy)| ((dx, dy), (dx + loc.0, dy + loc.1)))
-            .filter(|(_, (x, y))| input[*y as usize][*x as usize] != Loc::Wall)
+            .filter(|(_, nloc)| open(input, *nloc))
         {
             let nd = if facing == ndir { 1 + d } else { 1001 + d };
             if nd < current_lowest[nloc.1 as usize][nloc.0 as usize][dirtoind(ndir)] {
@@ -102,46 +95,10 @@ fn pathfind(start_loc: (i32, i32), end_loc: (i32, i32), input: &Input) -> u32 {
         }
     }
 
-    panic!("no path through maze")
+    Err(no_path())
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    let start_loc = input
-        .iter()
-        .enumerate()
-        .filter_map(|(row, line)| {
-            line.iter()
-                .enumerate()
-                .filter_map(|(col, loc)| match loc {
-                    Loc::Start => Some((col as i32, row as i32)),
-                    _ => None,
-                })
-                .next()
-        })
-        .next()
-        .unwrap();
-
-    let end_loc = input
-        .iter()
-        .enumerate()
-        .filter_map(|(row, line)| {
-            line.iter()
-                .enumerate()
-                .filter_map(|(col, loc)| match loc {
-                    Loc::End => Some((col as i32, row as i32)),
-                    _ => None,
-                })
-                .next()
-        })
-        .next()
-        .unwrap();
-
-    let distance = pathfindb(start_loc, end_loc, input);
-
-    Some(distance.to_string())
-}
-
-fn pathfindb(start_loc: (i32, i32), end_loc: (i32, i32), input: &Input) -> u32 {
+fn pathfindb(start_loc: (i32, i32), end_loc: (i32, i32), input: &Maze) -> Result<u32, AocError> {
     let mut current_lowest =
         vec![vec![vec![(u32::MAX, Vec::new()); 4]; input[0].len()]; input.len()];
     let mut queue: BinaryHeap<(Reverse<u32>, (i32, i32), (i32, i32))> = BinaryHeap::new();
@@ -150,11 +107,11 @@ fn pathfindb(start_loc: (i32, i32), end_loc: (i32, i32), input: &Input) -> u32 {
 
     queue.push((Reverse(0), (1, 0), start_loc));
 
-    let mut dist = 0;
+    let mut dist = None;
 
     while let Some((Reverse(prio), facing, loc)) = queue.pop() {
         if loc == end_loc {
-            dist = prio;
+            dist = Some(prio);
             break;
         }
 
@@ -163,7 +120,7 @@ fn pathfindb(start_loc: (i32, i32), end_loc: (i32, i32), input: &Input) -> u32 {
         for (ndir, nloc) in [(-1, 0), (1, 0), (0, 1), (0, -1)]
             .into_iter()
             .map(|(dx, dy)| ((dx, dy), (dx + loc.0, dy + loc.1)))
-            .filter(|(_, (x, y))| input[*y as usize][*x as usize] != Loc::Wall)
+            .filter(|(_, nloc)| open(input, *nloc))
         {
             let nd = if facing == ndir { 1 + d } else { 1001 + d };
             if nd < current_lowest[nloc.1 as usize][nloc.0 as usize][dirtoind(ndir)].0 {
@@ -185,6 +142,7 @@ fn pathfindb(start_loc: (i32, i32), end_loc: (i32, i32), input: &Input) -> u32 {
     //         .map_or(0, |row| row.get(37).map_or(0, |f| f[dirtoind((1, 0))].0))
     // );
 
+    let dist = dist.ok_or_else(no_path)?;
     let mut count = 0;
     let mut visited = vec![vec![bitvec![0; input[0].len()]; 4]; input.len()];
 
@@ -211,7 +169,7 @@ fn pathfindb(start_loc: (i32, i32), end_loc: (i32, i32), input: &Input) -> u32 {
     //     // line.iter().map(|a| (0..4).all(|f| )
     // }
 
-    count
+    Ok(count)
 }
 
 fn count_num_locs(
@@ -240,17 +198,39 @@ fn count_num_locs(
     }
 }
 
-fn exe(_input: &Input) {
-    todo!()
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        let maze = utils::grid(utils::lines(&input), |c| match c {
+            '.' => Some(Loc::Empty),
+            '#' => Some(Loc::Wall),
+            'S' => Some(Loc::Start),
+            'E' => Some(Loc::End),
+            _ => None,
+        })?;
+
+        let start = find_one(&maze, Loc::Start, 'S')?;
+        let end = find_one(&maze, Loc::End, 'E')?;
+        Ok(Input { maze, start, end })
+    }
+
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let distance = pathfind(input.start, input.end, &input.maze)?;
+
+        Ok(distance.into())
+    }
+
+    fn part_b(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let distance = pathfindb(input.start, input.end, &input.maze)?;
+
+        Ok(distance.into())
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 16, Day16);
 
 #[cfg(test)]
 mod tests {
@@ -259,10 +239,66 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day16-1", &DAY, Some("7036"), Some("45"), false);
-        utils::golden("day16-2", &DAY, Some("11048"), Some("64"), false);
-        utils::golden("day16-3", &DAY, Some("5078"), Some("413"), false);
-        utils::golden("day16-4", &DAY, Some("4006"), Some("8"), false);
+        utils::golden(
+            "2024/day16-1",
+            &Day16,
+            Some(Answer::Int(7036)),
+            Some(Answer::Int(45)),
+            false,
+        );
+        utils::golden(
+            "2024/day16-2",
+            &Day16,
+            Some(Answer::Int(11048)),
+            Some(Answer::Int(64)),
+            false,
+        );
+        utils::golden(
+            "2024/day16-3",
+            &Day16,
+            Some(Answer::Int(5078)),
+            Some(Answer::Int(413)),
+            false,
+        );
+        utils::golden(
+            "2024/day16-4",
+            &Day16,
+            Some(Answer::Int(4006)),
+            Some(Answer::Int(8)),
+            false,
+        );
+    }
+
+    #[test]
+    fn start_and_end() {
+        let parse = |text: &str| Day16.parse(text.to_string(), &Log::quiet());
+
+        assert!(matches!(
+            parse("####\n#.E#\n####\n"),
+            Err(AocError::Parse { line: 1, .. })
+        ));
+        assert!(matches!(
+            parse("####\n#SE#\n#S.#\n####\n"),
+            Err(AocError::Parse {
+                line: 3,
+                column: 2,
+                ..
+            })
+        ));
+        assert_eq!(parse("####\n#SE#\n####\n").unwrap().end, (2, 1));
+    }
+
+    #[test]
+    fn walled_in() {
+        let input = Day16.parse("#S#E#\n".to_string(), &Log::quiet()).unwrap();
+        let params = Params::new(&[], &[]).unwrap();
+
+        for part in [Day16::part_a, Day16::part_b] {
+            assert!(matches!(
+                part(&Day16, &input, &params, &Log::quiet()),
+                Err(AocError::Solve(_))
+            ));
+        }
     }
 
     #[test]
@@ -275,6 +311,6 @@ mod tests {
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(16, &DAY, Some("95444"), Some("513"), false);
+        utils::finalanswer(2024, 16, &Day16, false);
     }
 }
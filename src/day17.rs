@@ -1,6 +1,11 @@
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
+use crate::utils;
 use itertools::Itertools;
-use once_cell::sync::Lazy;
 
 #[derive(Debug, Clone)]
 pub struct Input {
@@ -25,32 +30,6 @@ impl Input {
     }
 }
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    let mut lines = input.lines();
-    let a = lines.next().unwrap()[12..].parse::<usize>().unwrap();
-    let b = lines.next().unwrap()[12..].parse::<usize>().unwrap();
-    let c = lines.next().unwrap()[12..].parse::<usize>().unwrap();
-    lines.next();
-    let program = lines.next().unwrap()[9..]
-        .split(',')
-        .map(|c| c.parse::<u8>().unwrap())
-        .collect_vec();
-
-    Ok(Input { a, b, c, program })
-}
-
-fn part_a(input: &Input) -> Option<String> {
-    let loc = 0;
-
-    let mut input = input.clone();
-
-    let res = simulate(loc, &mut input);
-
-    let res = res.iter().map(|a| a.to_string()).join(",");
-
-    Some(res)
-}
-
 fn simulate(mut loc: usize, input: &mut Input) -> Vec<u8> {
     let mut res = Vec::new();
 
@@ -158,42 +137,21 @@ fn simulate_till_out(mut loc: usize, input: &mut Input) -> u8 {
     panic!("no out")
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    // specialized to my input
-    // assumes a is only divede by 8 during the course of the program before looping
-    // also assumes that the program only loops at the end
-
-    let a = search(input.program.len() - 1, 0, input).unwrap();
-
-    //    for i in input.program.iter().rev() {
-    //        println!("a before shift {a}");
-    //        a <<= 3;
-    //        println!("a after shift {a}");
-    //        for p in 0..8usize {
-    //            let mut input = input.clone();
-    //            input.a = dbg!(p + a);
-    //            if *i == simulate_till_out(0, &mut input) {
-    //                println!("a before add: {a}");
-    //                a += dbg!(p);
-    //                println!("a after add: {a}");
-    //                println!("hello");
-    ////                break;
-    //            }
-    //        }
-    //        println!("done loop")
-    //    }
-
-    Some(a.to_string())
-}
-
-fn search(i: usize, a: usize, input: &Input) -> Option<usize> {
+fn search(i: usize, a: usize, input: &Input, log: &Log) -> Option<usize> {
     for p in 0..8usize {
         let mut input2 = input.clone();
         input2.a = (a << 3) + p;
         if input.program[i] == simulate_till_out(0, &mut input2) {
+            log.trace(|| {
+                format!(
+                    "a = {} outputs {} for instruction {i}",
+                    (a << 3) + p,
+                    input.program[i]
+                )
+            });
             if i == 0 {
                 return Some((a << 3) + p);
-            } else if let Some(val) = search(i - 1, (a << 3) + p, input) {
+            } else if let Some(val) = search(i - 1, (a << 3) + p, input, log) {
                 return Some(val);
             }
         }
@@ -202,17 +160,76 @@ fn search(i: usize, a: usize, input: &Input) -> Option<usize> {
     None
 }
 
-fn exe(_input: &Input) {
-    todo!()
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        let mut lines = utils::lines(&input);
+        let mut consumed = 0;
+        let mut next = |prefix: &str| {
+            // a missing line is reported where it should have been
+            let line = lines.next().ok_or_else(|| {
+                AocError::parse(consumed + 1, 1, "", format!("expected {:?}", prefix))
+            })?;
+            consumed += 1;
+            Ok::<_, AocError>((line, line.strip(line.text, prefix)?))
+        };
+
+        let (line, a) = next("Register A: ")?;
+        let a = line.num::<usize>(a)?;
+        let (line, b) = next("Register B: ")?;
+        let b = line.num::<usize>(b)?;
+        let (line, c) = next("Register C: ")?;
+        let c = line.num::<usize>(c)?;
+        next("")?;
+        let (line, program) = next("Program: ")?;
+        let fields = program.split(',').collect::<Vec<_>>();
+        let program = fields
+            .iter()
+            .map(|c| match line.num::<u8>(c)? {
+                value @ 0..=7 => Ok(value),
+                _ => Err(line.error(c, "opcodes and operands are 0 to 7")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // adv, bst, out, bdv and cdv take combo operands, where 7 is reserved
+        for (i, pair) in program.chunks_exact(2).enumerate() {
+            if matches!(pair, [0 | 2 | 5 | 6 | 7, 7]) {
+                return Err(line.error(fields[2 * i + 1], "combo operand 7 is reserved"));
+            }
+        }
+
+        Ok(Input { a, b, c, program })
+    }
+
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let loc = 0;
+
+        let mut input = input.clone();
+
+        let res = simulate(loc, &mut input);
+
+        let res = res.iter().map(|a| a.to_string()).join(",");
+
+        Ok(res.into())
+    }
+
+    fn part_b(&self, input: &Input, _params: &Params, log: &Log) -> Result<Answer, AocError> {
+        // specialized to my input
+        // assumes a is only divede by 8 during the course of the program before looping
+        // also assumes that the program only loops at the end
+
+        let a = search(input.program.len() - 1, 0, input, log).ok_or_else(|| {
+            AocError::Solve("no value of register a makes the program output itself".to_string())
+        })?;
+
+        Ok(a.into())
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 17, Day17);
 
 // Register A: 52042868
 // Register B: 0
@@ -239,12 +256,68 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day17", &DAY, Some("4,6,3,5,6,3,5,2,1,0"), None, false);
-        utils::golden("day17-2", &DAY, None, Some("117440"), false);
+        utils::golden(
+            "2024/day17",
+            &Day17,
+            Some(Answer::Str("4,6,3,5,6,3,5,2,1,0".to_string())),
+            None,
+            false,
+        );
+        utils::golden(
+            "2024/day17-2",
+            &Day17,
+            None,
+            Some(Answer::Int(117440)),
+            false,
+        );
+    }
+
+    #[test]
+    fn missing_lines() {
+        let parse = |text: &str| Day17.parse(text.to_string(), &Log::quiet());
+
+        assert!(matches!(
+            parse("Register A: 1\nRegister B: 2\n"),
+            Err(AocError::Parse { line: 3, .. })
+        ));
+        assert!(matches!(
+            parse("Register A: 1\nRegister B: 2\nRegister C: 3\n\n"),
+            Err(AocError::Parse { line: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn bad_programs() {
+        let parse = |program: &str| {
+            Day17.parse(
+                format!("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: {program}\n"),
+                &Log::quiet(),
+            )
+        };
+
+        assert!(parse("0,1,5,4,3,0").is_ok());
+        assert!(matches!(
+            parse("9,1"),
+            Err(AocError::Parse {
+                line: 5,
+                column: 10,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse("0,1,5,7"),
+            Err(AocError::Parse {
+                line: 5,
+                column: 16,
+                ..
+            })
+        ));
+        // 7 is fine as a literal operand
+        assert!(parse("1,7,5,4").is_ok());
     }
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(17, &DAY, Some("2,1,0,1,7,2,5,0,3"), Some("267265166222235"), false);
+        utils::finalanswer(2024, 17, &Day17, false);
     }
 }
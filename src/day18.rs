@@ -1,45 +1,33 @@
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::{Param, Params};
+use crate::registry;
+use crate::utils;
 use bitvec::bitvec;
 use bitvec::vec::BitVec;
-use itertools::Itertools;
-use once_cell::sync::Lazy;
 use std::collections::{BinaryHeap, VecDeque};
 
 type Input = Vec<(usize, usize)>;
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    Ok(input
-        .lines()
-        .map(|line| {
-            let (bef, aft) = line.split_once(',').unwrap();
-            (bef.parse::<usize>().unwrap(), aft.parse::<usize>().unwrap())
-        })
-        .collect_vec())
-}
-
-fn part_a(input: &Input) -> Option<String> {
-    Some(part_a_with(1024, 70, input))
-}
-
-fn part_a_with(num: usize, max: usize, input: &Input) -> String {
+fn part_a_with(num: usize, max: usize, input: &Input) -> Option<u32> {
     let mut locs: Vec<BitVec> = vec![bitvec![0; max + 1]; max + 1];
 
     for (x, y) in input[0..num].iter() {
         locs[*y].set(*x, true);
     }
 
-    let len = shortest_path(&locs);
-
-    len.to_string()
+    shortest_path(&locs)
 }
 
-fn shortest_path(locs: &[BitVec]) -> u32 {
+fn shortest_path(locs: &[BitVec]) -> Option<u32> {
     let mut queue = VecDeque::from([(0i32, 0i32, 0u32)]);
     let mut visited = vec![bitvec![0; locs.len()]; locs.len()];
 
     while let Some((x, y, d)) = queue.pop_front() {
         if x as usize == locs.len() - 1 && y as usize == locs.len() - 1 {
-            return d;
+            return Some(d);
         }
 
         for (dx, dy) in [(-1, 0), (1, 0), (0, 1), (0, -1)].into_iter() {
@@ -60,7 +48,7 @@ fn shortest_path(locs: &[BitVec]) -> u32 {
         }
     }
 
-    panic!("no path found")
+    None
 }
 
 fn path_exist(locs: &[BitVec]) -> bool {
@@ -124,34 +112,87 @@ fn path_exist(locs: &[BitVec]) -> bool {
     false
 }
 
-fn part_b_with(max: usize, input: &Input) -> String {
+fn part_b_with(max: usize, input: &Input) -> Option<(usize, usize)> {
     let mut locs = vec![bitvec![0; max + 1]; max + 1];
 
     for (_, (x, y)) in input.iter().enumerate() {
         locs[*y].set(*x, true);
         if !path_exist(&locs) {
-            return format!("{x},{y}");
+            return Some((*x, *y));
         }
     }
 
-    "".to_string()
+    None
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    Some(part_b_with(70, input))
+/// the bytes that fall, an error when they do not fit the parameters
+fn falling(input: &Input, bytes: usize, size: usize) -> Result<&[(usize, usize)], AocError> {
+    let falling = input.get(..bytes).ok_or_else(|| {
+        AocError::Config(format!(
+            "bytes is {bytes} but the input only has {}",
+            input.len()
+        ))
+    })?;
+    match falling.iter().find(|(x, y)| *x > size || *y > size) {
+        Some((x, y)) => Err(AocError::Config(format!(
+            "{x},{y} is outside a grid of size {size}"
+        ))),
+        None => Ok(falling),
+    }
 }
 
-fn exe(_input: &Input) {
-    todo!()
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        utils::lines(&input)
+            .map(|line| {
+                let (bef, aft) = line.split(line.text, ",")?;
+                Ok((line.num::<usize>(bef)?, line.num::<usize>(aft)?))
+            })
+            .collect()
+    }
+
+    fn part_a(&self, input: &Input, params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let bytes = params.get_as::<usize>("bytes")?;
+        let size = params.get_as::<usize>("size")?;
+        falling(input, bytes, size)?;
+
+        part_a_with(bytes, size, input)
+            .map(Answer::from)
+            .ok_or_else(|| AocError::Solve("the exit can not be reached".to_string()))
+    }
+
+    fn part_b(&self, input: &Input, params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let size = params.get_as::<usize>("size")?;
+        falling(input, input.len(), size)?;
+
+        part_b_with(size, input)
+            .map(|(x, y)| Answer::Coord(x as i64, y as i64))
+            .ok_or_else(|| AocError::Solve("the exit is never cut off".to_string()))
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param {
+                name: "bytes",
+                default: 1024,
+                min: 0,
+                max: u32::MAX.into(),
+            },
+            Param {
+                name: "size",
+                default: 70,
+                min: 0,
+                max: 10000,
+            },
+        ]
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 18, Day18);
 
 #[cfg(test)]
 mod tests {
@@ -160,24 +201,54 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::set_function(
-            "day18",
-            &parser,
-            &|input: &Input| part_a_with(12, 6, input),
-            "22",
-            false,
-        );
-        utils::set_function(
-            "day18",
-            &parser,
-            &|input: &Input| part_b_with(6, input),
-            "6,1",
+        utils::golden_params(
+            "2024/day18",
+            &Day18,
+            &[("bytes", 12), ("size", 6)],
+            Some(Answer::Int(22)),
+            Some(Answer::Coord(6, 1)),
             false,
         );
     }
 
+    #[test]
+    fn parse_error_location() {
+        match Day18.parse("5,4\n4,x2\n".to_string(), &Log::quiet()) {
+            Err(AocError::Parse {
+                line, column, text, ..
+            }) => {
+                assert_eq!((line, column, text.as_str()), (2, 3, "x2"))
+            }
+            other => panic!("expected parse error got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn params_that_do_not_fit() {
+        let input = vec![(1, 2), (7, 0)];
+
+        assert!(falling(&input, 1, 6).is_ok());
+        assert!(matches!(falling(&input, 3, 6), Err(AocError::Config(_))));
+        assert!(matches!(falling(&input, 2, 6), Err(AocError::Config(_))));
+    }
+
+    #[test]
+    fn walled_in() {
+        let input = vec![(1, 0), (0, 1)];
+        let params = Params::new(
+            &Day18.params(),
+            &[("bytes".to_string(), 2), ("size".to_string(), 2)],
+        )
+        .unwrap();
+
+        assert!(matches!(
+            Day18.part_a(&input, &params, &Log::quiet()),
+            Err(AocError::Solve(_))
+        ));
+    }
+
     #[test]
     fn finalanswer() {
-        utils::finalanswer(18, &DAY, Some("276"), Some("60,37"), false);
+        utils::finalanswer(2024, 18, &Day18, false);
     }
 }
@@ -1,50 +1,14 @@
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
 use itertools::Itertools;
-use once_cell::sync::Lazy;
 use regex::Regex;
 
 type Input = (Vec<String>, Vec<String>);
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    let mut lines = input.lines();
-    let towels = lines
-        .next()
-        .unwrap()
-        .split(", ")
-        .map(|a| a.to_string())
-        .collect_vec();
-    lines.next();
-
-    let displays = lines.map(|a| a.to_string()).collect_vec();
-
-    Ok((towels, displays))
-}
-
-fn part_a(input: &Input) -> Option<String> {
-    let matcher = Regex::new(&format!("^({})*$", input.0.join("|"))).unwrap();
-
-    let res = input.1.iter().filter(|line| matcher.is_match(line)).count();
-
-    Some(res.to_string())
-}
-
-fn part_b(input: &Input) -> Option<String> {
-    let words = input
-        .0
-        .iter()
-        .map(|p| p.chars().collect_vec())
-        .collect_vec();
-
-    Some(
-        input
-            .1
-            .iter()
-            .map(|a| num_matches(a, &words))
-            .sum::<u64>()
-            .to_string(),
-    )
-}
-
 fn num_matches(a: &str, towels: &[Vec<char>]) -> u64 {
     let a = a.chars().collect_vec();
     let mut matches = vec![0; a.len()];
@@ -72,17 +36,48 @@ fn num_matches(a: &str, towels: &[Vec<char>]) -> u64 {
     matches[0]
 }
 
-fn exe(_input: &Input) {
-    todo!()
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        let mut lines = input.lines();
+        let towels = lines
+            .next()
+            .ok_or_else(|| AocError::parse(1, 1, "", "empty input"))?
+            .split(", ")
+            .map(|a| a.to_string())
+            .collect_vec();
+        lines.next();
+
+        let displays = lines.map(|a| a.to_string()).collect_vec();
+
+        Ok((towels, displays))
+    }
+
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let matcher = Regex::new(&format!("^({})*$", input.0.join("|"))).unwrap();
+
+        let res = input.1.iter().filter(|line| matcher.is_match(line)).count();
+
+        Ok(res.into())
+    }
+
+    fn part_b(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let words = input
+            .0
+            .iter()
+            .map(|p| p.chars().collect_vec())
+            .collect_vec();
+
+        Ok(Answer::from(
+            input.1.iter().map(|a| num_matches(a, &words)).sum::<u64>(),
+        ))
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 19, Day19);
 
 #[cfg(test)]
 mod tests {
@@ -91,7 +86,13 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day19", &DAY, Some("6"), Some("16"), false)
+        utils::golden(
+            "2024/day19",
+            &Day19,
+            Some(Answer::Int(6)),
+            Some(Answer::Int(16)),
+            false,
+        )
     }
 
     #[test]
@@ -117,6 +118,6 @@ mod tests {
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(19, &DAY, Some("338"), Some("841533074412361"), false);
+        utils::finalanswer(2024, 19, &Day19, false);
     }
 }
@@ -1,25 +1,15 @@
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
+use crate::utils;
 use itertools::Itertools;
 use itertools::*;
-use once_cell::sync::Lazy;
 
 type Input = Vec<Vec<i32>>;
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    input
-        .lines()
-        .map(|row| {
-            row.split_whitespace()
-                .map(|a| a.parse::<i32>().map_err(|err| err.to_string()))
-                .process_results(|row| row.collect_vec())
-        })
-        .process_results(|colm| colm.collect_vec())
-}
-
-fn part_a(input: &Input) -> Option<String> {
-    Some(input.iter().filter(|row| is_safe(row)).count().to_string())
-}
-
 fn is_safe(row: &[i32]) -> bool {
     let mut all_pos = true;
     let mut all_neg = true;
@@ -46,10 +36,6 @@ fn is_safe(row: &[i32]) -> bool {
     return true;
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    Some(input.iter().filter(|row| is_safe2(row)).count().to_string())
-}
-
 fn is_safe_from(rowfrom: impl IntoIterator<Item = i32>, pos: bool, neg: bool) -> bool {
     let mut all_pos = pos;
     let mut all_neg = neg;
@@ -139,17 +125,36 @@ fn is_safe2(row: &[i32]) -> bool {
     return true;
 }
 
-fn exe(_input: &Input) {
-    todo!()
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        utils::lines(&input)
+            .map(|line| {
+                line.text
+                    .split_whitespace()
+                    .map(|a| line.num::<i32>(a))
+                    .process_results(|row| row.collect_vec())
+            })
+            .process_results(|colm| colm.collect_vec())
+    }
+
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        Ok(Answer::from(
+            input.iter().filter(|row| is_safe(row)).count(),
+        ))
+    }
+
+    fn part_b(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        Ok(Answer::from(
+            input.iter().filter(|row| is_safe2(row)).count(),
+        ))
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 2, Day2);
 
 #[cfg(test)]
 mod tests {
@@ -162,12 +167,18 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day2", &DAY, Some("2"), Some("4"), false)
+        utils::golden(
+            "2024/day2",
+            &Day2,
+            Some(Answer::Int(2)),
+            Some(Answer::Int(4)),
+            false,
+        )
     }
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(2, &DAY, Some("402"), Some("455"), false)
+        utils::finalanswer(2024, 2, &Day2, false)
     }
 
     prop_compose! {
@@ -1,6 +1,10 @@
-use crate::day;
-use itertools::Itertools;
-use once_cell::sync::Lazy;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::{Param, Params};
+use crate::registry;
+use crate::utils;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Loc {
@@ -12,27 +16,6 @@ pub enum Loc {
 
 type Input = Vec<Vec<Loc>>;
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    Ok(input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '#' => Loc::Wall,
-                    'S' => Loc::Start,
-                    'E' => Loc::End,
-                    '.' => Loc::Empty,
-                    _ => panic!("invalid input"),
-                })
-                .collect_vec()
-        })
-        .collect_vec())
-}
-
-fn part_a(input: &Input) -> Option<String> {
-    Some(parts_with(100, 2, input))
-}
-
 struct Path<'a> {
     x: i32,
     lx: i32,
@@ -82,7 +65,7 @@ fn path_from(x: usize, y: usize, input: &Input) -> Path {
     }
 }
 
-fn parts_with(cutoff: u32, md: i32, input: &Input) -> String {
+fn parts_with(cutoff: u32, md: i32, input: &Input) -> usize {
     let start_loc = input
         .iter()
         .enumerate()
@@ -119,7 +102,7 @@ fn parts_with(cutoff: u32, md: i32, input: &Input) -> String {
         check_skipsb(&mut skips, cutoff, &distances, x, y, input, md);
     }
 
-    skips.to_string()
+    skips
 }
 
 fn check_skipsb(
@@ -153,21 +136,64 @@ fn check_skipsb(
     *skips += num_skip;
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    Some(parts_with(100, 20, input))
-}
+pub struct Day20;
 
-fn exe(_input: &Input) {
-    todo!()
+impl Solution for Day20 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        utils::grid(utils::lines(&input), |c| match c {
+            '#' => Some(Loc::Wall),
+            'S' => Some(Loc::Start),
+            'E' => Some(Loc::End),
+            '.' => Some(Loc::Empty),
+            _ => None,
+        })
+    }
+
+    fn part_a(&self, input: &Input, params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        Ok(parts_with(
+            params.get_as::<u32>("cutoff")?,
+            params.get_as::<i32>("cheat_a")?,
+            input,
+        )
+        .into())
+    }
+
+    fn part_b(&self, input: &Input, params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        Ok(parts_with(
+            params.get_as::<u32>("cutoff")?,
+            params.get_as::<i32>("cheat_b")?,
+            input,
+        )
+        .into())
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param {
+                name: "cutoff",
+                default: 100,
+                min: 0,
+                max: 1000000,
+            },
+            Param {
+                name: "cheat_a",
+                default: 2,
+                min: 0,
+                max: 1000,
+            },
+            Param {
+                name: "cheat_b",
+                default: 20,
+                min: 0,
+                max: 1000,
+            },
+        ]
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 20, Day20);
 
 #[cfg(test)]
 mod tests {
@@ -176,25 +202,27 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::set_function(
-            "day20",
-            &parser,
-            &|input| parts_with(12, 2, input),
-            "8",
+        utils::golden_params(
+            "2024/day20",
+            &Day20,
+            &[("cutoff", 12)],
+            Some(Answer::Int(8)),
+            None,
             false,
         );
 
-        utils::set_function(
-            "day20",
-            &parser,
-            &|input| parts_with(50, 20, input),
-            "285",
+        utils::golden_params(
+            "2024/day20-2",
+            &Day20,
+            &[("cutoff", 50)],
+            None,
+            Some(Answer::Int(285)),
             false,
         );
     }
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(20, &DAY, Some("1530"), Some("1033983"), false);
+        utils::finalanswer(2024, 20, &Day20, false);
     }
 }
@@ -1,15 +1,17 @@
-use crate::day;
-use itertools::{chain, repeat_n, Itertools};
-use once_cell::sync::Lazy;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
+use crate::utils;
+use itertools::{chain, repeat_n};
 use std::cmp;
 use std::collections::HashMap;
 use typed_arena::Arena;
 
-type Input = Vec<String>;
-
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    Ok(input.lines().map(|a| a.to_string()).collect_vec())
-}
+// each code with its numeric part
+type Input = Vec<(String, u64)>;
 
 struct NumPad<'a> {
     // might not need to store it but doing it for now in case I do need to
@@ -146,7 +148,7 @@ impl<'a> NumPad<'a> {
                 '8' => (1, 0),
                 '9' => (2, 0),
                 'A' => (2, 3),
-                _ => panic!("invalid character"),
+                _ => unreachable!("parse only lets digits and A through"),
             };
 
             let dx = x.abs_diff(nx);
@@ -221,20 +223,6 @@ impl FinalKeyPad {
     }
 }
 
-fn part_a(input: &Input) -> Option<String> {
-    let bump = Arena::new();
-    let mut keypad = FinalKeyPad::new();
-    let mut keypad = make_key_pad(2, &mut keypad, &bump);
-
-    Some(
-        input
-            .iter()
-            .map(|a| keypad.num_dist(&a) * a[0..a.len() - 1].parse::<u64>().unwrap())
-            .sum::<u64>()
-            .to_string(),
-    )
-}
-
 fn make_key_pad<'a>(num: usize, f: &'a mut FinalKeyPad, bump: &'a Arena<KeyPad<'a>>) -> NumPad<'a> {
     let mut current: &mut dyn KeyPadTrait = f;
 
@@ -245,48 +233,100 @@ fn make_key_pad<'a>(num: usize, f: &'a mut FinalKeyPad, bump: &'a Arena<KeyPad<'
     NumPad::new(current)
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    let bump = Arena::new();
-    let mut keypad = FinalKeyPad::new();
-    let mut keypad = make_key_pad(25, &mut keypad, &bump);
-
-    Some(
-        input
-            .iter()
-            .map(|a| keypad.num_dist(&a) * a[0..a.len() - 1].parse::<u64>().unwrap())
-            .sum::<u64>()
-            .to_string(),
-    )
-}
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        utils::lines(&input)
+            .map(|line| {
+                let digits = line
+                    .text
+                    .strip_suffix('A')
+                    .ok_or_else(|| line.error(line.text, "expected a code like 029A"))?;
+                if let Some((i, c)) = digits.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+                    return Err(line.error(&digits[i..i + c.len_utf8()], "expected a digit"));
+                }
+                if digits.is_empty() {
+                    return Err(line.error(line.text, "expected digits before the A"));
+                }
+                Ok((line.text.to_string(), line.num::<u64>(digits)?))
+            })
+            .collect()
+    }
+
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let bump = Arena::new();
+        let mut keypad = FinalKeyPad::new();
+        let mut keypad = make_key_pad(2, &mut keypad, &bump);
+
+        Ok(Answer::from(
+            input
+                .iter()
+                .map(|(code, num)| keypad.num_dist(code) * num)
+                .sum::<u64>(),
+        ))
+    }
+
+    fn part_b(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let bump = Arena::new();
+        let mut keypad = FinalKeyPad::new();
+        let mut keypad = make_key_pad(25, &mut keypad, &bump);
 
-fn exe(_input: &Input) {
-    todo!()
+        Ok(Answer::from(
+            input
+                .iter()
+                .map(|(code, num)| keypad.num_dist(code) * num)
+                .sum::<u64>(),
+        ))
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 21, Day21);
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils;
 
     #[test]
     fn goldens() {
         utils::golden(
-            "day21",
-            &DAY,
-            Some("126384"),
-            Some("154115708116294"),
+            "2024/day21",
+            &Day21,
+            Some(Answer::Int(126384)),
+            Some(Answer::Int(154115708116294)),
             false,
         )
     }
 
+    #[test]
+    fn bad_codes() {
+        let parse = |text: &str| Day21.parse(text.to_string(), &Log::quiet());
+
+        assert!(matches!(
+            parse("029A\n029B\n"),
+            Err(AocError::Parse {
+                line: 2,
+                column: 1,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse("029A\n\n"),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            parse("0x9A\n"),
+            Err(AocError::Parse {
+                line: 1,
+                column: 2,
+                ..
+            })
+        ));
+        assert!(matches!(parse("A\n"), Err(AocError::Parse { line: 1, .. })));
+    }
+
     #[test]
     fn t029a() {
         let bump = Arena::new();
@@ -307,6 +347,6 @@ mod tests {
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(21, &DAY, Some("123096"), Some("154517692795352"), false);
+        utils::finalanswer(2024, 21, &Day21, false);
     }
 }
@@ -1,19 +1,17 @@
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
+use crate::utils;
 use intmap::IntMap;
 use itertools::Itertools;
-use once_cell::sync::Lazy;
 use rayon::iter::IntoParallelRefIterator;
 use rayon::iter::ParallelIterator;
 
 type Input = Vec<u64>;
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    Ok(input
-        .lines()
-        .map(|a| a.parse::<u64>().unwrap())
-        .collect_vec())
-}
-
 fn sim_step(mut a: u64) -> u64 {
     a ^= a * 64;
     a %= 16777216;
@@ -31,16 +29,6 @@ fn sim_steps(mut a: u64, steps: u64) -> u64 {
     a
 }
 
-fn part_a(input: &Input) -> Option<String> {
-    Some(
-        input
-            .iter()
-            .map(|num| sim_steps(*num, 2000))
-            .sum::<u64>()
-            .to_string(),
-    )
-}
-
 #[derive(Debug, Clone, Copy)]
 struct Trader {
     secret: u64,
@@ -91,43 +79,51 @@ fn add_num(num: u64, map: &mut IntMap<u64>) {
     }
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    let map = input
-        .par_iter()
-        .map(|num| {
-            let mut map: IntMap<u64> = IntMap::new();
-            add_num(*num, &mut map);
-            map
-        })
-        .reduce(
-            || IntMap::new(),
-            |mut m1, m2| {
-                for (k, v) in m2.into_iter() {
-                    match m1.get_mut(k) {
-                        Some(loc) => *loc += v,
-                        None => {
-                            m1.insert(k, v);
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        utils::lines(&input)
+            .map(|line| line.num::<u64>(line.text))
+            .collect()
+    }
+
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        Ok(Answer::from(
+            input.iter().map(|num| sim_steps(*num, 2000)).sum::<u64>(),
+        ))
+    }
+
+    fn part_b(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let map = input
+            .par_iter()
+            .map(|num| {
+                let mut map: IntMap<u64> = IntMap::new();
+                add_num(*num, &mut map);
+                map
+            })
+            .reduce(
+                || IntMap::new(),
+                |mut m1, m2| {
+                    for (k, v) in m2.into_iter() {
+                        match m1.get_mut(k) {
+                            Some(loc) => *loc += v,
+                            None => {
+                                m1.insert(k, v);
+                            }
                         }
                     }
-                }
-                m1
-            },
-        );
+                    m1
+                },
+            );
 
-    Some(map.values().max().unwrap().to_string())
-}
-
-fn exe(_input: &Input) {
-    todo!()
+        Ok((*map.values().max().unwrap()).into())
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 22, Day22);
 
 #[cfg(test)]
 mod tests {
@@ -136,8 +132,14 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day22", &DAY, Some("37327623"), None, false);
-        utils::golden("day22-2", &DAY, None, Some("23"), false);
+        utils::golden(
+            "2024/day22",
+            &Day22,
+            Some(Answer::Int(37327623)),
+            None,
+            false,
+        );
+        utils::golden("2024/day22-2", &Day22, None, Some(Answer::Int(23)), false);
     }
 
     #[test]
@@ -147,6 +149,6 @@ mod tests {
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(22, &DAY, Some("20506453102"), Some("2423"), false);
+        utils::finalanswer(2024, 22, &Day22, false);
     }
 }
@@ -1,47 +1,15 @@
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
+use crate::utils;
 use itertools::Itertools;
-use once_cell::sync::Lazy;
 use std::collections::{HashMap, HashSet};
 
 type Input = Vec<(String, String)>;
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    Ok(input
-        .lines()
-        .map(|line| {
-            let (bef, aft) = line.split_once('-').unwrap();
-            (bef.to_string(), aft.to_string())
-        })
-        .collect_vec())
-}
-
-fn part_a(input: &Input) -> Option<String> {
-    let mut graph: HashMap<&str, Vec<&str>> = HashMap::new();
-
-    for (a, b) in input {
-        graph.entry(&a).or_insert_with(|| Vec::new()).push(&b);
-        graph.entry(&b).or_insert_with(|| Vec::new()).push(&a);
-    }
-
-    for v in graph.values_mut() {
-        v.sort_unstable()
-    }
-
-    let mut found: HashSet<(&str, &str, &str)> = HashSet::new();
-
-    let empty_vec = Vec::new();
-
-    for loc in graph.keys().filter(|l| l.chars().next().unwrap() == 't') {
-        let a = graph.get(loc).unwrap_or(&empty_vec);
-        for connected in a.iter() {
-            let c = graph.get(connected).unwrap_or(&empty_vec);
-            add_shared(&mut found, c, connected, a, loc)
-        }
-    }
-
-    Some(found.len().to_string())
-}
-
 // assumes that the &strs in a and c are already sorted
 fn add_shared<'a, 'b>(
     found: &'b mut HashSet<(&'a str, &'a str, &'a str)>,
@@ -71,30 +39,6 @@ fn add_shared<'a, 'b>(
     }
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    let mut graph: HashMap<&str, HashSet<&str>> = HashMap::new();
-    for (a, b) in input {
-        graph.entry(&a).or_insert_with(|| HashSet::new()).insert(&b);
-        graph.entry(&b).or_insert_with(|| HashSet::new()).insert(&a);
-    }
-
-    let mut max_clique = HashSet::new();
-
-    bronkerbosch(
-        &mut HashSet::new(),
-        graph.keys().map(|c| *c).collect::<HashSet<&str>>(),
-        HashSet::with_capacity(graph.len()),
-        &graph,
-        &mut max_clique,
-    );
-
-    let mut max_clique: Vec<&str> = max_clique.iter().map(|s| *s).collect_vec();
-
-    max_clique.sort_unstable();
-
-    Some(max_clique.join(","))
-}
-
 fn bronkerbosch<'a>(
     r: &mut HashSet<&'a str>,
     mut p: HashSet<&'a str>,
@@ -134,17 +78,73 @@ fn bronkerbosch<'a>(
         x.insert(v);
     }
 }
-fn exe(_input: &Input) {
-    todo!()
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        utils::lines(&input)
+            .map(|line| {
+                let (bef, aft) = line.split(line.text, "-")?;
+                Ok((bef.to_string(), aft.to_string()))
+            })
+            .collect()
+    }
+
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let mut graph: HashMap<&str, Vec<&str>> = HashMap::new();
+
+        for (a, b) in input {
+            graph.entry(&a).or_insert_with(|| Vec::new()).push(&b);
+            graph.entry(&b).or_insert_with(|| Vec::new()).push(&a);
+        }
+
+        for v in graph.values_mut() {
+            v.sort_unstable()
+        }
+
+        let mut found: HashSet<(&str, &str, &str)> = HashSet::new();
+
+        let empty_vec = Vec::new();
+
+        for loc in graph.keys().filter(|l| l.chars().next().unwrap() == 't') {
+            let a = graph.get(loc).unwrap_or(&empty_vec);
+            for connected in a.iter() {
+                let c = graph.get(connected).unwrap_or(&empty_vec);
+                add_shared(&mut found, c, connected, a, loc)
+            }
+        }
+
+        Ok(found.len().into())
+    }
+
+    fn part_b(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let mut graph: HashMap<&str, HashSet<&str>> = HashMap::new();
+        for (a, b) in input {
+            graph.entry(&a).or_insert_with(|| HashSet::new()).insert(&b);
+            graph.entry(&b).or_insert_with(|| HashSet::new()).insert(&a);
+        }
+
+        let mut max_clique = HashSet::new();
+
+        bronkerbosch(
+            &mut HashSet::new(),
+            graph.keys().map(|c| *c).collect::<HashSet<&str>>(),
+            HashSet::with_capacity(graph.len()),
+            &graph,
+            &mut max_clique,
+        );
+
+        let mut max_clique: Vec<&str> = max_clique.iter().map(|s| *s).collect_vec();
+
+        max_clique.sort_unstable();
+
+        Ok(max_clique.join(",").into())
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 23, Day23);
 
 #[cfg(test)]
 mod tests {
@@ -153,11 +153,17 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day23", &DAY, Some("7"), Some("co,de,ka,ta"), false)
+        utils::golden(
+            "2024/day23",
+            &Day23,
+            Some(Answer::Int(7)),
+            Some(Answer::Str("co,de,ka,ta".to_string())),
+            false,
+        )
     }
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(23, &DAY, Some("1184"), Some("hf,hz,lb,lm,ls,my,ps,qu,ra,uc,vi,xz,yv"), false);
+        utils::finalanswer(2024, 23, &Day23, false);
     }
 }
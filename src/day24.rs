@@ -1,8 +1,16 @@
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
+use crate::utils;
+use clap::Parser;
 use itertools::Itertools;
-use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Op {
@@ -13,63 +21,6 @@ pub enum Op {
 
 type Input = (Vec<(String, bool)>, Vec<(String, Op, String, String)>);
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    let (init, ops) = input.split_once("\n\n").unwrap();
-    let init = init
-        .lines()
-        .map(|r| {
-            let (name, val) = r.split_once(": ").unwrap();
-            (name.to_string(), val == "1")
-        })
-        .collect_vec();
-    let ops = ops
-        .lines()
-        .map(|l| {
-            let mut l = l.split(' ');
-            let a = l.next().unwrap().to_string();
-            let op = match l.next().unwrap() {
-                "AND" => Op::And,
-                "OR" => Op::Or,
-                "XOR" => Op::XOr,
-                _ => panic!("invalid value"),
-            };
-            let b = l.next().unwrap().to_string();
-            let _ = l.next();
-            let r = l.next().unwrap().to_string();
-            (a, op, b, r)
-        })
-        .collect_vec();
-
-    Ok((init, ops))
-}
-
-fn part_a(input: &Input) -> Option<String> {
-    let mut vals: HashMap<&str, bool> = HashMap::new();
-    let mut waiting_on: HashMap<&str, Vec<usize>> = HashMap::new();
-
-    for (id, (a, _, b, _)) in input.1.iter().enumerate() {
-        waiting_on.entry(&a).or_insert(Vec::new()).push(id);
-        waiting_on.entry(&b).or_insert(Vec::new()).push(id);
-    }
-
-    for (name, val) in input.0.iter() {
-        vals.insert(&name, *val);
-        update_waiting(&input.1, &mut waiting_on, &mut vals, &name);
-    }
-
-    let mut cur = 0;
-    let mut cur_val = 0;
-
-    let mut it = (0..).map(|a| vals.get(format!("z{:0>2}", a).as_str()));
-
-    while let Some(val) = it.next().unwrap() {
-        cur_val += (*val as u64) << cur;
-        cur += 1;
-    }
-
-    Some(cur_val.to_string())
-}
-
 fn update_waiting<'a>(
     input: &'a [(String, Op, String, String)],
     waiting_on: &HashMap<&'a str, Vec<usize>>,
@@ -117,46 +68,6 @@ enum Origin {
     Input(usize),
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    let mut vals: HashMap<&str, Origin> = HashMap::new();
-    let mut waiting_on: HashMap<&str, Vec<usize>> = HashMap::new();
-    let mut to_update: Vec<&str> = Vec::new();
-    let mut bad_wires: HashSet<&str> = HashSet::new();
-
-    for (id, (a, _, b, c)) in input.1.iter().enumerate() {
-        waiting_on.entry(&a).or_insert(Vec::new()).push(id);
-        waiting_on.entry(&b).or_insert(Vec::new()).push(id);
-        waiting_on.entry(&c).or_insert(Vec::new()).push(id);
-
-        if c == "z45" {
-            vals.insert(c, Origin::CircutLoc(CircutLoc::Or(44)));
-            to_update.push(c);
-        } else if c == "z00" {
-            vals.insert(c, Origin::CircutLoc(CircutLoc::HalfXOr));
-            to_update.push(c);
-        } else if c.chars().next().unwrap() == 'z' {
-            let l = c[1..].parse::<usize>().unwrap();
-            vals.insert(c, Origin::CircutLoc(CircutLoc::XOr2(l)));
-            to_update.push(c);
-        }
-    }
-
-    for (id, _) in input.0.iter() {
-        vals.insert(id, Origin::Input(id[1..].parse::<usize>().unwrap()));
-        update_waitingb(&input.1, &waiting_on, &mut vals, id, &mut bad_wires);
-    }
-
-    for u in to_update.into_iter() {
-        update_waitingb(&input.1, &waiting_on, &mut vals, u, &mut bad_wires);
-    }
-
-    let mut bad_wires = bad_wires.into_iter().collect_vec();
-
-    bad_wires.sort_unstable();
-
-    Some(bad_wires.join(","))
-}
-
 fn update_waitingb<'a>(
     input: &'a [(String, Op, String, String)],
     waiting_on: &HashMap<&'a str, Vec<usize>>,
@@ -391,8 +302,137 @@ fn expected_circut_loc_o(c: Origin) -> CircutLoc {
     }
 }
 
-fn exe(input: &Input) {
-    print!("digraph {{ ");
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        let mut lines = utils::lines(&input);
+
+        let init = lines
+            .by_ref()
+            .take_while(|line| !line.text.is_empty())
+            .map(|line| {
+                let (name, val) = line.split(line.text, ": ")?;
+                Ok((name.to_string(), val == "1"))
+            })
+            .collect::<Result<Vec<_>, AocError>>()?;
+        let ops = lines
+            .map(|line| {
+                let mut l = line.text.split(' ');
+                let mut next = || {
+                    l.next()
+                        .ok_or_else(|| line.error(line.text, "expected `a OP b -> r`"))
+                };
+                let a = next()?.to_string();
+                let op = match next()? {
+                    "AND" => Op::And,
+                    "OR" => Op::Or,
+                    "XOR" => Op::XOr,
+                    op => return Err(line.error(op, "invalid operator")),
+                };
+                let b = next()?.to_string();
+                let _ = next()?;
+                let r = next()?.to_string();
+                Ok((a, op, b, r))
+            })
+            .collect::<Result<Vec<_>, AocError>>()?;
+
+        Ok((init, ops))
+    }
+
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let mut vals: HashMap<&str, bool> = HashMap::new();
+        let mut waiting_on: HashMap<&str, Vec<usize>> = HashMap::new();
+
+        for (id, (a, _, b, _)) in input.1.iter().enumerate() {
+            waiting_on.entry(&a).or_insert(Vec::new()).push(id);
+            waiting_on.entry(&b).or_insert(Vec::new()).push(id);
+        }
+
+        for (name, val) in input.0.iter() {
+            vals.insert(&name, *val);
+            update_waiting(&input.1, &mut waiting_on, &mut vals, &name);
+        }
+
+        let mut cur = 0;
+        let mut cur_val = 0;
+
+        let mut it = (0..).map(|a| vals.get(format!("z{:0>2}", a).as_str()));
+
+        while let Some(val) = it.next().unwrap() {
+            cur_val += (*val as u64) << cur;
+            cur += 1;
+        }
+
+        Ok(cur_val.into())
+    }
+
+    fn part_b(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let mut vals: HashMap<&str, Origin> = HashMap::new();
+        let mut waiting_on: HashMap<&str, Vec<usize>> = HashMap::new();
+        let mut to_update: Vec<&str> = Vec::new();
+        let mut bad_wires: HashSet<&str> = HashSet::new();
+
+        for (id, (a, _, b, c)) in input.1.iter().enumerate() {
+            waiting_on.entry(&a).or_insert(Vec::new()).push(id);
+            waiting_on.entry(&b).or_insert(Vec::new()).push(id);
+            waiting_on.entry(&c).or_insert(Vec::new()).push(id);
+
+            if c == "z45" {
+                vals.insert(c, Origin::CircutLoc(CircutLoc::Or(44)));
+                to_update.push(c);
+            } else if c == "z00" {
+                vals.insert(c, Origin::CircutLoc(CircutLoc::HalfXOr));
+                to_update.push(c);
+            } else if c.chars().next().unwrap() == 'z' {
+                let l = c[1..].parse::<usize>().unwrap();
+                vals.insert(c, Origin::CircutLoc(CircutLoc::XOr2(l)));
+                to_update.push(c);
+            }
+        }
+
+        for (id, _) in input.0.iter() {
+            vals.insert(id, Origin::Input(id[1..].parse::<usize>().unwrap()));
+            update_waitingb(&input.1, &waiting_on, &mut vals, id, &mut bad_wires);
+        }
+
+        for u in to_update.into_iter() {
+            update_waitingb(&input.1, &waiting_on, &mut vals, u, &mut bad_wires);
+        }
+
+        let mut bad_wires = bad_wires.into_iter().collect_vec();
+
+        bad_wires.sort_unstable();
+
+        Ok(bad_wires.join(",").into())
+    }
+
+    fn visualize(
+        &self,
+        input: &Input,
+        _params: &Params,
+        args: &[String],
+    ) -> Option<Result<(), AocError>> {
+        Some(graph(input, args))
+    }
+}
+
+/// what exe --day 24 writes, the circuit as a graphviz digraph
+#[derive(Parser)]
+struct Graph {
+    /// write the graph to this file instead of stdout
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+fn graph(input: &Input, args: &[String]) -> Result<(), AocError> {
+    let Some(graph) = utils::visualize_args::<Graph>(24, args)? else {
+        return Ok(());
+    };
+
+    let mut dot = "digraph { ".to_string();
 
     for (a, o, b, c) in input.1.iter() {
         let sh = match o {
@@ -406,21 +446,23 @@ fn exe(input: &Input) {
             Op::XOr => "blue",
             Op::Or => "green",
         };
-        print!("{c} [shape=\"{sh}\" color=\"{col}\"]; ");
-        print!("{a} -> {c}; ");
-        print!("{b} -> {c}; ");
+        dot += &format!("{c} [shape=\"{sh}\" color=\"{col}\"]; ");
+        dot += &format!("{a} -> {c}; ");
+        dot += &format!("{b} -> {c}; ");
     }
 
-    println!("}}")
+    dot += "}";
+
+    match graph.out {
+        Some(path) => fs::write(&path, dot + "\n").map_err(|err| AocError::io(&path, err)),
+        None => {
+            println!("{dot}");
+            Ok(())
+        }
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 24, Day24);
 
 #[cfg(test)]
 mod tests {
@@ -429,18 +471,12 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day24-1", &DAY, Some("4"), None, false);
-        utils::golden("day24-2", &DAY, Some("2024"), None, false);
+        utils::golden("2024/day24-1", &Day24, Some(Answer::Int(4)), None, false);
+        utils::golden("2024/day24-2", &Day24, Some(Answer::Int(2024)), None, false);
     }
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(
-            24,
-            &DAY,
-            Some("57344080719736"),
-            Some("cgq,fnr,kqk,nbc,svm,z15,z23,z39"),
-            false,
-        );
+        utils::finalanswer(2024, 24, &Day24, false);
     }
 }
@@ -1,80 +1,79 @@
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
 use itertools::Itertools;
-use once_cell::sync::Lazy;
 
 type Input = (Vec<Vec<u16>>, Vec<Vec<u16>>);
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    let mut keys = Vec::new();
-    let mut locks = Vec::new();
+fn fits(a: &[u16], b: &[u16]) -> bool {
+    a.iter().zip(b.iter()).all(|(a, b)| a + b <= 7)
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Input;
 
-    for t in input.split("\n\n") {
-        let t = t.lines().map(|l| l.chars().collect_vec()).collect_vec();
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        let mut keys = Vec::new();
+        let mut locks = Vec::new();
 
-        if t[0].iter().all(|k| *k == '#') {
-            let mut lock = Vec::new();
-            for c in 0..t[0].len() {
-                let mut num = 0;
-                for r in 0..t.len() {
-                    if t[r][c] == '#' {
-                        num += 1;
-                    } else {
-                        break;
+        for t in input.split("\n\n") {
+            let t = t.lines().map(|l| l.chars().collect_vec()).collect_vec();
+
+            if t[0].iter().all(|k| *k == '#') {
+                let mut lock = Vec::new();
+                for c in 0..t[0].len() {
+                    let mut num = 0;
+                    for r in 0..t.len() {
+                        if t[r][c] == '#' {
+                            num += 1;
+                        } else {
+                            break;
+                        }
                     }
+                    lock.push(num);
                 }
-                lock.push(num);
-            }
-            locks.push(lock);
-        } else {
-            let mut key = Vec::new();
-            for c in 0..t[0].len() {
-                let mut num = 0;
-                for r in (0..t.len()).rev() {
-                    if t[r][c] == '#' {
-                        num += 1;
-                    } else {
-                        break;
+                locks.push(lock);
+            } else {
+                let mut key = Vec::new();
+                for c in 0..t[0].len() {
+                    let mut num = 0;
+                    for r in (0..t.len()).rev() {
+                        if t[r][c] == '#' {
+                            num += 1;
+                        } else {
+                            break;
+                        }
                     }
+                    key.push(num);
                 }
-                key.push(num);
+                keys.push(key);
             }
-            keys.push(key);
         }
-    }
-
-    Ok((keys, locks))
-}
 
-fn fits(a: &[u16], b: &[u16]) -> bool {
-    a.iter().zip(b.iter()).all(|(a, b)| a + b <= 7)
-}
-
-fn part_a(input: &Input) -> Option<String> {
-    Some(
-        input
-            .0
-            .iter()
-            .map(|key| input.1.iter().filter(|lock| fits(&key, &lock)).count())
-            .sum::<usize>()
-            .to_string(),
-    )
-}
+        Ok((keys, locks))
+    }
 
-fn part_b(_input: &Input) -> Option<String> {
-    None
-}
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        Ok(Answer::from(
+            input
+                .0
+                .iter()
+                .map(|key| input.1.iter().filter(|lock| fits(&key, &lock)).count())
+                .sum::<usize>(),
+        ))
+    }
 
-fn exe(_input: &Input) {
-    todo!()
+    fn part_b(&self, _input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        Ok(Answer::NotImplemented)
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 25, Day25);
 
 #[cfg(test)]
 mod tests {
@@ -83,11 +82,11 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day25", &DAY, Some("3"), None, false)
+        utils::golden("2024/day25", &Day25, Some(Answer::Int(3)), None, false)
     }
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(25, &DAY, Some("3483"), None, false);
+        utils::finalanswer(2024, 25, &Day25, false);
     }
 }
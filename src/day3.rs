@@ -1,84 +1,81 @@
-use crate::day;
-use once_cell::sync::Lazy;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
 use regex::Regex;
 
 type Input = String;
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    Ok(input)
-}
+pub struct Day3;
 
-fn part_a(input: &Input) -> Option<String> {
-    let re = Regex::new("mul\\((\\d{1,3}),(\\d{1,3})\\)").unwrap();
+impl Solution for Day3 {
+    type Input = Input;
 
-    let res = re
-        .captures_iter(input)
-        .map(|c| c.extract())
-        .map(|(_, [a, b])| a.parse::<u32>().unwrap() * b.parse::<u32>().unwrap())
-        .sum::<u32>();
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        Ok(input)
+    }
 
-    Some(res.to_string())
-}
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let re = Regex::new("mul\\((\\d{1,3}),(\\d{1,3})\\)").unwrap();
 
-fn part_b(input: &Input) -> Option<String> {
-    let re = Regex::new("mul\\(\\d{1,3},\\d{1,3}\\)|do\\(\\)|don't\\(\\)").unwrap();
+        let res = re
+            .captures_iter(input)
+            .map(|c| c.extract())
+            .map(|(_, [a, b])| a.parse::<u32>().unwrap() * b.parse::<u32>().unwrap())
+            .sum::<u32>();
 
-    let mut mult = true;
-    let mut sum = 0;
+        Ok(res.into())
+    }
 
-    for i in re.find_iter(input).map(|a| a.as_str()) {
-        if i.starts_with("don") {
-            mult = false;
-        } else if i.starts_with('d') {
-            mult = true;
+    fn part_b(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let re = Regex::new("mul\\(\\d{1,3},\\d{1,3}\\)|do\\(\\)|don't\\(\\)").unwrap();
+
+        let mut mult = true;
+        let mut sum = 0;
+
+        for i in re.find_iter(input).map(|a| a.as_str()) {
+            if i.starts_with("don") {
+                mult = false;
+            } else if i.starts_with('d') {
+                mult = true;
+            }
+
+            if mult && i.starts_with("mul") {
+                let (a, b) = i
+                    .split_once('(')
+                    .unwrap()
+                    .1
+                    .split_once(')')
+                    .unwrap()
+                    .0
+                    .split_once(',')
+                    .unwrap();
+
+                sum += a.parse::<u32>().unwrap() * b.parse::<u32>().unwrap();
+            }
         }
 
-        if mult && i.starts_with("mul") {
-            let (a, b) = i
-                .split_once('(')
-                .unwrap()
-                .1
-                .split_once(')')
-                .unwrap()
-                .0
-                .split_once(',')
-                .unwrap();
-
-            sum += a.parse::<u32>().unwrap() * b.parse::<u32>().unwrap();
-        }
+        Ok(sum.into())
     }
-
-    Some(sum.to_string())
-}
-
-fn exe(_input: &Input) {
-    todo!()
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 3, Day3);
 
 #[cfg(test)]
 mod tests {
-    
-    
-    
 
     use super::*;
     use crate::utils;
 
     #[test]
     fn goldens() {
-        utils::golden("day3", &DAY, Some("161"), None, false)
+        utils::golden("2024/day3", &Day3, Some(Answer::Int(161)), None, false)
     }
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(3, &DAY, Some("179571322"), Some("103811193"), false)
+        utils::finalanswer(2024, 3, &Day3, false)
     }
 }
@@ -1,16 +1,13 @@
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
 use itertools::Itertools;
-use once_cell::sync::Lazy;
 
 type Input = Vec<Vec<char>>;
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    Ok(input
-        .lines()
-        .map(|line| line.chars().collect_vec())
-        .collect_vec())
-}
-
 fn ismatch(i: i32, j: i32, di: i32, dj: i32, input: &[Vec<char>]) -> bool {
     "XMAS".chars().enumerate().all(|(ind, c)| {
         input
@@ -26,18 +23,6 @@ fn countmatches(i: i32, j: i32, input: &[Vec<char>]) -> usize {
         .count()
 }
 
-fn part_a(input: &Input) -> Option<String> {
-    let mut matches = 0;
-
-    for i in 0..input.len() {
-        for j in 0..input.len() {
-            matches += countmatches(i as i32, j as i32, input)
-        }
-    }
-
-    Some(matches.to_string())
-}
-
 fn ismatchx(i: usize, j: usize, input: &[Vec<char>]) -> bool {
     if input[i][j] != 'A' {
         return false;
@@ -62,31 +47,46 @@ fn ismatchx(i: usize, j: usize, input: &[Vec<char>]) -> bool {
     return true;
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    let mut matches = 0;
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Input;
 
-    for i in 0..input.len() {
-        for j in 0..input.len() {
-            if ismatchx(i, j, input) {
-                matches += 1;
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        Ok(input
+            .lines()
+            .map(|line| line.chars().collect_vec())
+            .collect_vec())
+    }
+
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let mut matches = 0;
+
+        for i in 0..input.len() {
+            for j in 0..input.len() {
+                matches += countmatches(i as i32, j as i32, input)
             }
         }
+
+        Ok(matches.into())
     }
 
-    Some(matches.to_string())
-}
+    fn part_b(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let mut matches = 0;
 
-fn exe(_input: &Input) {
-    todo!()
+        for i in 0..input.len() {
+            for j in 0..input.len() {
+                if ismatchx(i, j, input) {
+                    matches += 1;
+                }
+            }
+        }
+
+        Ok(matches.into())
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 4, Day4);
 
 #[cfg(test)]
 mod tests {
@@ -95,11 +95,17 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day4", &DAY, Some("18"), Some("9"), false)
+        utils::golden(
+            "2024/day4",
+            &Day4,
+            Some(Answer::Int(18)),
+            Some(Answer::Int(9)),
+            false,
+        )
     }
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(4, &DAY, Some("2496"), Some("1967"), false);
+        utils::finalanswer(2024, 4, &Day4, false);
     }
 }
@@ -1,32 +1,14 @@
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
+use crate::utils;
 use inttable::IntTable;
-use itertools::Itertools;
-use once_cell::sync::Lazy;
 
 type Input = (Vec<(u64, u64)>, Vec<Vec<u64>>);
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    let (b, a) = input.split_once("\n\n").unwrap();
-
-    let before_conds = b
-        .lines()
-        .map(|line| {
-            let (a, b) = line.split_once('|').unwrap();
-            (a.parse::<u64>().unwrap(), b.parse::<u64>().unwrap())
-        })
-        .collect_vec();
-    let after_rows = a
-        .lines()
-        .map(|line| {
-            line.split(',')
-                .map(|n| n.parse::<u64>().unwrap())
-                .collect_vec()
-        })
-        .collect_vec();
-
-    Ok((before_conds, after_rows))
-}
-
 fn valid_edit(edit: &Vec<u64>, after: &IntTable<Vec<u64>>) -> bool {
     let mut used: IntTable<()> = IntTable::with_capacity(edit.len() * 2);
 
@@ -42,23 +24,6 @@ fn valid_edit(edit: &Vec<u64>, after: &IntTable<Vec<u64>>) -> bool {
     true
 }
 
-fn part_a(input: &Input) -> Option<String> {
-    let mut after = IntTable::new();
-
-    for (bef, aft) in input.0.iter() {
-        after.entry(*bef).or_insert(Vec::new()).push(*aft)
-    }
-
-    let res: u64 = input
-        .1
-        .iter()
-        .filter(|line| valid_edit(&line, &after))
-        .map(|line| line[line.len() / 2])
-        .sum();
-
-    Some(res.to_string())
-}
-
 fn fix_edit(edit: &Vec<u64>, after: &IntTable<Vec<u64>>) -> Vec<u64> {
     let mut used: IntTable<()> = IntTable::with_capacity(edit.len() * 2);
     let mut res = Vec::with_capacity(edit.len());
@@ -75,13 +40,19 @@ fn fix_edit(edit: &Vec<u64>, after: &IntTable<Vec<u64>>) -> Vec<u64> {
     res
 }
 
-fn dfs(res: &mut Vec<u64>, used: &mut IntTable<()>, after: &IntTable<Vec<u64>>, contains: &IntTable<()>, i: u64) {
+fn dfs(
+    res: &mut Vec<u64>,
+    used: &mut IntTable<()>,
+    after: &IntTable<Vec<u64>>,
+    contains: &IntTable<()>,
+    i: u64,
+) {
     if used.contains_key(i) {
         return;
     }
 
     if !contains.contains_key(i) {
-	return;
+        return;
     }
 
     used.insert(i, ());
@@ -93,35 +64,66 @@ fn dfs(res: &mut Vec<u64>, used: &mut IntTable<()>, after: &IntTable<Vec<u64>>,
     res.push(i)
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    let mut after = IntTable::new();
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        let mut lines = utils::lines(&input);
 
-    for (bef, aft) in input.0.iter() {
-        after.entry(*bef).or_insert(Vec::new()).push(*aft)
+        let before_conds = lines
+            .by_ref()
+            .take_while(|line| !line.text.is_empty())
+            .map(|line| {
+                let (a, b) = line.split(line.text, "|")?;
+                Ok((line.num::<u64>(a)?, line.num::<u64>(b)?))
+            })
+            .collect::<Result<Vec<_>, AocError>>()?;
+        let after_rows = lines
+            .map(|line| line.text.split(',').map(|n| line.num::<u64>(n)).collect())
+            .collect::<Result<Vec<_>, AocError>>()?;
+
+        Ok((before_conds, after_rows))
     }
 
-    let res: u64 = input
-        .1
-        .iter()
-        .filter(|line| !valid_edit(line, &after))
-        .map(|line| fix_edit(line, &after))
-        .map(|line| line[line.len() / 2])
-        .sum();
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let mut after = IntTable::new();
 
-    Some(res.to_string())
-}
+        for (bef, aft) in input.0.iter() {
+            after.entry(*bef).or_insert(Vec::new()).push(*aft)
+        }
+
+        let res: u64 = input
+            .1
+            .iter()
+            .filter(|line| valid_edit(&line, &after))
+            .map(|line| line[line.len() / 2])
+            .sum();
+
+        Ok(res.into())
+    }
 
-fn exe(_input: &Input) {
-    todo!()
+    fn part_b(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let mut after = IntTable::new();
+
+        for (bef, aft) in input.0.iter() {
+            after.entry(*bef).or_insert(Vec::new()).push(*aft)
+        }
+
+        let res: u64 = input
+            .1
+            .iter()
+            .filter(|line| !valid_edit(line, &after))
+            .map(|line| fix_edit(line, &after))
+            .map(|line| line[line.len() / 2])
+            .sum();
+
+        Ok(res.into())
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 5, Day5);
 
 #[cfg(test)]
 mod tests {
@@ -130,10 +132,16 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day5", &DAY, Some("143"), Some("123"), false)
+        utils::golden(
+            "2024/day5",
+            &Day5,
+            Some(Answer::Int(143)),
+            Some(Answer::Int(123)),
+            false,
+        )
     }
     #[test]
     fn finalanswer() {
-        utils::finalanswer(5, &DAY, Some("5713"), Some("5180"), false);
+        utils::finalanswer(2024, 5, &Day5, false);
     }
 }
@@ -1,36 +1,17 @@
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
 use bitvec::bitvec;
 use bitvec::vec::BitVec;
 // use itertools::Itertools;
-use once_cell::sync::Lazy;
 use rayon::iter::ParallelBridge;
 use rayon::iter::ParallelIterator;
 
 type Input = (Vec<BitVec>, (usize, usize));
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    let loc = input
-        .lines()
-        .enumerate()
-        .filter_map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .filter(|(_, c)| *c == '^')
-                .next()
-                .map(|(x, _)| (x, y))
-        })
-        .next()
-        .unwrap();
-
-    Ok((
-        input
-            .lines()
-            .map(|line| line.chars().map(|c| c == '#').collect::<BitVec>())
-            .collect::<Vec<_>>(),
-        loc,
-    ))
-}
-
 fn find_num(
     field: &Vec<BitVec>,
     mut visited: Vec<BitVec>,
@@ -78,26 +59,6 @@ fn advance(field: &Vec<BitVec>, x: &mut i32, y: &mut i32, dx: &mut i32, dy: &mut
     advance(field, x, y, dx, dy);
 }
 
-fn part_a(input: &Input) -> Option<String> {
-    let field = &input.0;
-
-    let visited = vec![bitvec![0; field[0].len()]; field.len()];
-
-    let mut res = 0;
-
-    find_num(
-        field,
-        visited,
-        input.1 .0 as i32,
-        input.1 .1 as i32,
-        0,
-        -1,
-        &mut res,
-    );
-
-    Some(res.to_string())
-}
-
 #[derive(Copy, Clone, Debug)]
 struct Pos {
     n: bool,
@@ -293,34 +254,73 @@ fn advance_find_split(
     advance_find_split(field, visited, x, y, dx, dy)
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    let res = ParallelIterator::sum::<u32>(
-        findsplits(&input.0, input.1 .0 as i32, input.1 .1 as i32, 0, -1)
-            .par_bridge()
-            .map(|(x, y, dx, dy)| {
-                let field = input.0.clone();
-                if checkloop(&field, x, y, dx, dy) {
-                    1
-                } else {
-                    0
-                }
-            }),
-    );
-
-    Some(res.to_string())
-}
+pub struct Day6;
 
-fn exe(_input: &Input) {
-    todo!()
+impl Solution for Day6 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        let loc = input
+            .lines()
+            .enumerate()
+            .filter_map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c == '^')
+                    .next()
+                    .map(|(x, _)| (x, y))
+            })
+            .next()
+            .ok_or_else(|| AocError::parse(1, 1, "", "no guard '^' in the map"))?;
+
+        Ok((
+            input
+                .lines()
+                .map(|line| line.chars().map(|c| c == '#').collect::<BitVec>())
+                .collect::<Vec<_>>(),
+            loc,
+        ))
+    }
+
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let field = &input.0;
+
+        let visited = vec![bitvec![0; field[0].len()]; field.len()];
+
+        let mut res = 0;
+
+        find_num(
+            field,
+            visited,
+            input.1 .0 as i32,
+            input.1 .1 as i32,
+            0,
+            -1,
+            &mut res,
+        );
+
+        Ok(res.into())
+    }
+
+    fn part_b(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let res = ParallelIterator::sum::<u32>(
+            findsplits(&input.0, input.1 .0 as i32, input.1 .1 as i32, 0, -1)
+                .par_bridge()
+                .map(|(x, y, dx, dy)| {
+                    let field = input.0.clone();
+                    if checkloop(&field, x, y, dx, dy) {
+                        1
+                    } else {
+                        0
+                    }
+                }),
+        );
+
+        Ok(res.into())
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 6, Day6);
 
 #[cfg(test)]
 mod tests {
@@ -329,12 +329,17 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day6", &DAY, Some("41"), Some("6"), false)
+        utils::golden(
+            "2024/day6",
+            &Day6,
+            Some(Answer::Int(41)),
+            Some(Answer::Int(6)),
+            false,
+        )
     }
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(6, &DAY, Some("5444"), Some("1946"), false);
+        utils::finalanswer(2024, 6, &Day6, false);
     }
-
 }
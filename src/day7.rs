@@ -1,34 +1,15 @@
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
+use crate::utils;
 use itertools::Itertools;
-use once_cell::sync::Lazy;
 use std::cmp;
 
 type Input = Vec<(u64, Vec<u64>)>;
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    Ok(input
-        .lines()
-        .map(|line| {
-            let (before, after) = line.split_once(": ").unwrap();
-            let equation = after
-                .split(' ')
-                .map(|a| a.parse::<u64>().unwrap())
-                .collect_vec();
-            (before.parse::<u64>().unwrap(), equation)
-        })
-        .collect_vec())
-}
-
-fn part_a(input: &Input) -> Option<String> {
-    let res = input
-        .iter()
-        .filter(|(r, vs)| solvable(*r, vs))
-        .map(|(r, _)| r)
-        .sum::<u64>();
-
-    Some(res.to_string())
-}
-
 fn solvable(r: u64, vs: &[u64]) -> bool {
     let upper = vs[0..] // upper bound
         .iter()
@@ -93,16 +74,6 @@ fn check_solvable(
     check_solvable(target, next, upper, lower, rev, i - 1)
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    let res = input
-        .iter()
-        .filter(|(r, vs)| solvableb(*r, vs))
-        .map(|(r, _)| r)
-        .sum::<u64>();
-
-    Some(res.to_string())
-}
-
 fn digits(b: u64) -> u32 {
     b.ilog(10) + 1
 }
@@ -176,7 +147,6 @@ fn check_solvableb(
         }
     }
 
-
     if current % 10u64.pow(digits(rev[i])) == rev[i] {
         let next = current / 10u64.pow(digits(rev[i]));
 
@@ -198,34 +168,69 @@ fn check_solvableb(
     check_solvableb(target, next, upper, lower, rev, i - 1)
 }
 
-fn exe(_input: &Input) {
-    todo!()
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        utils::lines(&input)
+            .map(|line| {
+                let (before, after) = line.split(line.text, ": ")?;
+                let equation = after
+                    .split(' ')
+                    .map(|a| line.num::<u64>(a))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok((line.num::<u64>(before)?, equation))
+            })
+            .collect()
+    }
+
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let res = input
+            .iter()
+            .filter(|(r, vs)| solvable(*r, vs))
+            .map(|(r, _)| r)
+            .sum::<u64>();
+
+        Ok(res.into())
+    }
+
+    fn part_b(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let res = input
+            .iter()
+            .filter(|(r, vs)| solvableb(*r, vs))
+            .map(|(r, _)| r)
+            .sum::<u64>();
+
+        Ok(res.into())
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 7, Day7);
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::utils;
-    
+
     use proptest::collection;
     use proptest::prelude::*;
 
     #[test]
     fn goldens() {
-        utils::golden("day7", &DAY, Some("3749"), Some("11387"), false)
+        utils::golden(
+            "2024/day7",
+            &Day7,
+            Some(Answer::Int(3749)),
+            Some(Answer::Int(11387)),
+            false,
+        )
     }
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(7, &DAY, Some("28730327770375"), Some("424977609625985"), false);
+        utils::finalanswer(2024, 7, &Day7, false);
     }
 
     #[derive(Debug, Clone)]
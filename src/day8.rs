@@ -1,39 +1,15 @@
-use crate::day;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
 use bitvec::vec::BitVec;
 use bitvec::*;
 use itertools::Itertools;
-use once_cell::sync::Lazy;
 
 type Input = Vec<Vec<char>>;
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    Ok(input
-        .lines()
-        .map(|line| line.chars().collect_vec())
-        .collect_vec())
-}
-
-fn part_a(input: &Input) -> Option<String> {
-    let h = input.len();
-    let w = input[0].len();
-
-    let mut set: Vec<BitVec> = vec![bitvec![0; w]; h];
-
-    let mut table: Vec<Vec<(usize, usize)>> = vec![Vec::new(); 256]; // no unicode support
-
-    let mut count = 0;
-
-    for (y, row) in input.iter().enumerate() {
-        for (x, loc) in row.iter().enumerate() {
-            if *loc != '.' {
-                add_location(&mut set, &mut table[*loc as usize], x, y, &mut count)
-            }
-        }
-    }
-
-    Some(count.to_string())
-}
-
 fn add_location(
     freqs_locs: &mut [BitVec],
     match_locs: &mut Vec<(usize, usize)>,
@@ -70,27 +46,6 @@ fn matching_loc(x: usize, y: usize, x1: usize, y1: usize) -> Option<(usize, usiz
     Some(((2 * x).checked_sub(x1)?, (2 * y).checked_sub(y1)?))
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    let h = input.len();
-    let w = input[0].len();
-
-    let mut set: Vec<BitVec> = vec![bitvec![0; w]; h];
-
-    let mut table: Vec<Vec<(usize, usize)>> = vec![Vec::new(); 256]; // no unicode support
-
-    let mut count = 0;
-
-    for (y, row) in input.iter().enumerate() {
-        for (x, loc) in row.iter().enumerate() {
-            if *loc != '.' {
-                add_locations(&mut set, &mut table[*loc as usize], x, y, &mut count)
-            }
-        }
-    }
-
-    Some(count.to_string())
-}
-
 fn add_locations(
     freqs_locs: &mut [BitVec],
     match_locs: &mut Vec<(usize, usize)>,
@@ -165,17 +120,62 @@ fn matching_locs(
     xs.zip(ys)
 }
 
-fn exe(_input: &Input) {
-    todo!()
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        Ok(input
+            .lines()
+            .map(|line| line.chars().collect_vec())
+            .collect_vec())
+    }
+
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let h = input.len();
+        let w = input[0].len();
+
+        let mut set: Vec<BitVec> = vec![bitvec![0; w]; h];
+
+        let mut table: Vec<Vec<(usize, usize)>> = vec![Vec::new(); 256]; // no unicode support
+
+        let mut count = 0;
+
+        for (y, row) in input.iter().enumerate() {
+            for (x, loc) in row.iter().enumerate() {
+                if *loc != '.' {
+                    add_location(&mut set, &mut table[*loc as usize], x, y, &mut count)
+                }
+            }
+        }
+
+        Ok(count.into())
+    }
+
+    fn part_b(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let h = input.len();
+        let w = input[0].len();
+
+        let mut set: Vec<BitVec> = vec![bitvec![0; w]; h];
+
+        let mut table: Vec<Vec<(usize, usize)>> = vec![Vec::new(); 256]; // no unicode support
+
+        let mut count = 0;
+
+        for (y, row) in input.iter().enumerate() {
+            for (x, loc) in row.iter().enumerate() {
+                if *loc != '.' {
+                    add_locations(&mut set, &mut table[*loc as usize], x, y, &mut count)
+                }
+            }
+        }
+
+        Ok(count.into())
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 8, Day8);
 
 #[cfg(test)]
 mod tests {
@@ -184,11 +184,17 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day8", &DAY, Some("14"), Some("34"), false)
+        utils::golden(
+            "2024/day8",
+            &Day8,
+            Some(Answer::Int(14)),
+            Some(Answer::Int(34)),
+            false,
+        )
     }
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(8, &DAY, Some("369"), Some("1169"), false);
+        utils::finalanswer(2024, 8, &Day8, false);
     }
 }
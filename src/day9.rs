@@ -1,21 +1,15 @@
 use std::collections::VecDeque;
 
-use crate::day;
-use itertools::Itertools;
-use once_cell::sync::Lazy;
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
+use crate::utils;
 
 type Input = Vec<u8>;
 
-fn parser(input: String, _verbose: bool) -> Result<Input, String> {
-    Ok(input
-        .lines()
-        .next() // to strip any trailing new lines
-        .unwrap()
-        .chars()
-        .map(|c| c.to_digit(10).unwrap() as u8)
-        .collect_vec())
-}
-
 struct GetNBack<'a> {
     input: &'a mut VecDeque<(usize, u8, bool)>,
     left: u8,
@@ -50,37 +44,6 @@ impl<'a> Iterator for GetNBack<'a> {
     }
 }
 
-fn part_a(input: &Input) -> Option<String> {
-    let mut input = input
-        .iter()
-        .zip([true, false].iter().cycle())
-        .enumerate()
-        .map(|(id, (num, used))| (id / 2, *num, *used))
-        .collect::<VecDeque<_>>();
-
-    let mut sum = 0;
-    let mut i = 0;
-
-    while !input.is_empty() {
-        let (id, num, used) = input.pop_front().unwrap();
-
-        let num = num as usize;
-
-        if used {
-            sum += id * (num * i + (num * (num - 1)) / 2);
-            i += num;
-        } else {
-            for (id, num) in get_nback(&mut input, num) {
-                let num = num as usize;
-                sum += id * (num * i + (num * (num - 1)) / 2);
-                i += num;
-            }
-        }
-    }
-
-    Some(sum.to_string())
-}
-
 fn get_nback(input: &mut VecDeque<(usize, u8, bool)>, num: usize) -> GetNBack {
     GetNBack {
         input,
@@ -88,43 +51,6 @@ fn get_nback(input: &mut VecDeque<(usize, u8, bool)>, num: usize) -> GetNBack {
     }
 }
 
-fn part_b(input: &Input) -> Option<String> {
-    let mut free_space = Vec::with_capacity(input.len() / 2 + 1);
-    let mut files = Vec::with_capacity(input.len() / 2 + 1);
-
-    let mut i = 0;
-
-    for (id, num, used) in input
-        .iter()
-        .zip([true, false].iter().cycle())
-        .enumerate()
-        .map(|(id, (num, used))| (id / 2, *num, *used))
-    {
-        if used {
-            files.push((id, num, i))
-        } else {
-            free_space.push((i, num))
-        }
-
-        i += num as usize;
-    }
-
-    let mut sum = 0;
-
-    for (id, num, loc) in files.into_iter().rev() {
-        let loc = match try_alloc_free_before(&mut free_space, num, loc) {
-            Some(loc) => loc,
-            None => loc,
-        };
-
-        let num = num as usize;
-
-        sum += id * (num * loc + (num * (num - 1)) / 2);
-    }
-
-    Some(sum.to_string())
-}
-
 fn try_alloc_free_before(free_space: &mut [(usize, u8)], num: u8, loc: usize) -> Option<usize> {
     for (floc, fnum) in free_space.iter_mut() {
         if *floc >= loc {
@@ -143,17 +69,96 @@ fn try_alloc_free_before(free_space: &mut [(usize, u8)], num: u8, loc: usize) ->
     return None;
 }
 
-fn exe(_input: &Input) {
-    todo!()
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        let line = utils::lines(&input)
+            .next() // to strip any trailing new lines
+            .ok_or_else(|| AocError::parse(1, 1, "", "empty input"))?;
+
+        line.text
+            .char_indices()
+            .map(|(i, c)| {
+                c.to_digit(10)
+                    .map(|d| d as u8)
+                    .ok_or_else(|| line.error(&line.text[i..i + c.len_utf8()], "expected a digit"))
+            })
+            .collect()
+    }
+
+    fn part_a(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let mut input = input
+            .iter()
+            .zip([true, false].iter().cycle())
+            .enumerate()
+            .map(|(id, (num, used))| (id / 2, *num, *used))
+            .collect::<VecDeque<_>>();
+
+        let mut sum = 0;
+        let mut i = 0;
+
+        while !input.is_empty() {
+            let (id, num, used) = input.pop_front().unwrap();
+
+            let num = num as usize;
+
+            if used {
+                sum += id * (num * i + (num * (num - 1)) / 2);
+                i += num;
+            } else {
+                for (id, num) in get_nback(&mut input, num) {
+                    let num = num as usize;
+                    sum += id * (num * i + (num * (num - 1)) / 2);
+                    i += num;
+                }
+            }
+        }
+
+        Ok(sum.into())
+    }
+
+    fn part_b(&self, input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        let mut free_space = Vec::with_capacity(input.len() / 2 + 1);
+        let mut files = Vec::with_capacity(input.len() / 2 + 1);
+
+        let mut i = 0;
+
+        for (id, num, used) in input
+            .iter()
+            .zip([true, false].iter().cycle())
+            .enumerate()
+            .map(|(id, (num, used))| (id / 2, *num, *used))
+        {
+            if used {
+                files.push((id, num, i))
+            } else {
+                free_space.push((i, num))
+            }
+
+            i += num as usize;
+        }
+
+        let mut sum = 0;
+
+        for (id, num, loc) in files.into_iter().rev() {
+            let loc = match try_alloc_free_before(&mut free_space, num, loc) {
+                Some(loc) => loc,
+                None => loc,
+            };
+
+            let num = num as usize;
+
+            sum += id * (num * loc + (num * (num - 1)) / 2);
+        }
+
+        Ok(sum.into())
+    }
 }
 
-pub static DAY: Lazy<day::Day<Input>> = Lazy::new(|| day::Day {
-    // do not touch
-    parser: Box::new(parser),
-    part_a: Box::new(part_a),
-    part_b: Box::new(part_b),
-    exe: Box::new(exe),
-});
+registry::register!(2024, 9, Day9);
 
 #[cfg(test)]
 mod tests {
@@ -162,16 +167,22 @@ mod tests {
 
     #[test]
     fn goldens() {
-        utils::golden("day9", &DAY, Some("1928"), Some("2858"), false)
+        utils::golden(
+            "2024/day9",
+            &Day9,
+            Some(Answer::Int(1928)),
+            Some(Answer::Int(2858)),
+            false,
+        )
     }
 
     // #[test]
     // fn evil_input() {
-    //     utils::golden("day9-evil", &DAY, None, Some("5799706413896802"), false);
+    //     utils::golden("2024/day9-evil", &Day9, None, Some(Answer::Int(5799706413896802)), false);
     // }
 
     #[test]
     fn finalanswer() {
-        utils::finalanswer(9, &DAY, Some("6367087064415"), Some("6390781891880"), false);
+        utils::finalanswer(2024, 9, &Day9, false);
     }
 }
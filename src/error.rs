@@ -0,0 +1,128 @@
+use crate::day::Part;
+use std::{error::Error, fmt, io, path::PathBuf, time::Duration};
+
+#[derive(Debug)]
+pub enum AocError {
+    /// reading or writing a file failed
+    Io { path: PathBuf, source: io::Error },
+    /// the request to adventofcode.com could not be made
+    Fetch { url: String, source: reqwest::Error },
+    /// adventofcode.com answered with a non success status
+    Http {
+        url: String,
+        status: reqwest::StatusCode,
+    },
+    /// missing or invalid configuration (fetch config, inputs directory, ...)
+    Config(String),
+    /// the puzzle input is malformed, line and column are 1 indexed
+    Parse {
+        line: usize,
+        column: usize,
+        text: String,
+        message: String,
+    },
+    /// a part could not produce an answer for the parsed input
+    Solve(String),
+    /// a part was still running when its time budget ran out
+    TimedOut { part: Part, limit: Duration },
+    /// the puzzle is not unlocked yet so there is nothing to fetch
+    Locked {
+        year: u32,
+        day: u32,
+        unlocks_in: Duration,
+    },
+    /// parsing or a part panicked, location is file:line:column when known
+    Panic {
+        message: String,
+        location: Option<String>,
+    },
+}
+
+impl AocError {
+    pub fn io(path: impl Into<PathBuf>, source: io::Error) -> AocError {
+        AocError::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    pub fn parse(
+        line: usize,
+        column: usize,
+        text: impl Into<String>,
+        message: impl Into<String>,
+    ) -> AocError {
+        AocError::Parse {
+            line,
+            column,
+            text: text.into(),
+            message: message.into(),
+        }
+    }
+
+    /// process exit code used by main when this error ends the run
+    /// (1 is left for generic failures and 2 is used by clap for bad arguments)
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AocError::Io { .. } => 3,
+            AocError::Fetch { .. } | AocError::Http { .. } => 4,
+            AocError::Config(_) => 5,
+            AocError::Parse { .. } => 6,
+            AocError::Solve(_) => 7,
+            AocError::TimedOut { .. } => 8,
+            AocError::Panic { .. } => 9,
+            AocError::Locked { .. } => 10,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Io { path, .. } => write!(f, "could not access {}", path.display()),
+            AocError::Fetch { url, .. } => write!(f, "failed to fetch {url}"),
+            AocError::Http { url, status } => write!(f, "fetching {url} returned {status}"),
+            AocError::Config(msg) => write!(f, "{msg}"),
+            AocError::Parse {
+                line,
+                column,
+                text,
+                message,
+            } => write!(
+                f,
+                "failed to parse input at line {line}, column {column}: {message} (found {text:?})"
+            ),
+            AocError::Solve(msg) => write!(f, "failed to solve: {msg}"),
+            AocError::TimedOut { part, limit } => {
+                write!(f, "part {part} timed out after {limit:.2?}")
+            }
+            AocError::Locked {
+                year,
+                day,
+                unlocks_in,
+            } => write!(
+                f,
+                "day {day} of {year} is not unlocked yet, it unlocks in {}",
+                crate::unlock::countdown(*unlocks_in)
+            ),
+            AocError::Panic {
+                message,
+                location: Some(location),
+            } => write!(f, "panicked at {location}: {message}"),
+            AocError::Panic {
+                message,
+                location: None,
+            } => write!(f, "panicked: {message}"),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            AocError::Fetch { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
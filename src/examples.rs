@@ -0,0 +1,226 @@
+use crate::day::{self, FetchConfig};
+use crate::error::AocError;
+use crate::unlock;
+use crate::watch::{expected_path, Expected};
+use chrono::Utc;
+use regex::Regex;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// an example input from the puzzle page with the answers the page gives for it
+#[derive(Debug, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub a: Option<String>,
+    pub b: Option<String>,
+}
+
+fn unescape(html: &str) -> String {
+    let text = Regex::new(r"<[^>]*>").unwrap().replace_all(html, "");
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// the examples of a puzzle page. each part's description (its <article>) uses its first
+/// <pre><code> block as the example and its last emphasized <code> as the answer to it,
+/// part two reuses the example of part one when it does not have its own
+pub fn extract(html: &str) -> Vec<Example> {
+    let article = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    let pre = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    let answer =
+        Regex::new(r"(?s)<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>").unwrap();
+
+    let mut examples: Vec<Example> = Vec::new();
+
+    for (part, article) in article.captures_iter(html).take(2).enumerate() {
+        let article = article.get(1).unwrap().as_str();
+
+        let input = pre.captures(article).map(|c| unescape(&c[1]));
+        // emphasis inside the examples is not an answer
+        let prose = pre.replace_all(article, "");
+        let found = answer
+            .captures_iter(&prose)
+            .last()
+            .and_then(|c| c.get(1).or(c.get(2)))
+            .map(|m| unescape(m.as_str()));
+
+        let example = match input {
+            Some(input) => match examples.iter().position(|e| e.input == input) {
+                Some(at) => &mut examples[at],
+                None => {
+                    examples.push(Example {
+                        input,
+                        a: None,
+                        b: None,
+                    });
+                    examples.last_mut().unwrap()
+                }
+            },
+            None => match examples.last_mut() {
+                Some(example) => example,
+                None => continue,
+            },
+        };
+
+        if part == 0 {
+            example.a = found;
+        } else {
+            example.b = found;
+        }
+    }
+
+    examples
+}
+
+/// downloads the puzzle page of the day, only has part two once part one is solved
+pub fn fetch_page(
+    base_url: &str,
+    conf: &FetchConfig,
+    year: u32,
+    day: u32,
+) -> Result<String, AocError> {
+    unlock::check_released(year, day, Utc::now())?;
+    day::fetch(&format!("{base_url}/{year}/day/{day}"), conf)
+}
+
+/// writes the examples to dir/day{N}-{k} with their answers in a .expected file next to each.
+/// nothing is written when one of the files is already there, unless force is set
+pub fn write(
+    dir: &Path,
+    day: u32,
+    examples: &[Example],
+    force: bool,
+) -> Result<Vec<PathBuf>, AocError> {
+    let files = examples
+        .iter()
+        .enumerate()
+        .flat_map(|(k, example)| {
+            let golden = dir.join(format!("day{day}-{}", k + 1));
+            let expected = Expected {
+                a: example.a.clone(),
+                b: example.b.clone(),
+                params: Vec::new(),
+            };
+            [
+                (expected_path(&golden), expected.to_string()),
+                (golden, example.input.clone()),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    if !force {
+        if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+            return Err(AocError::Config(format!(
+                "{} already exists, use --force to replace it",
+                path.display()
+            )));
+        }
+    }
+
+    fs::create_dir_all(dir).map_err(|err| AocError::io(dir, err))?;
+    for (path, contents) in files.iter() {
+        fs::write(path, contents).map_err(|err| AocError::io(path, err))?;
+    }
+
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::stand_in_server;
+    use crate::watch::parse_expected;
+    use std::{env, process};
+
+    fn fixture(name: &str) -> String {
+        fs::read_to_string(format!("fixtures/{name}")).unwrap()
+    }
+
+    #[test]
+    fn both_parts() {
+        assert_eq!(
+            extract(&fixture("day-page.html")),
+            vec![
+                Example {
+                    input: "3   4\n4   3\n2 < 5\n".to_string(),
+                    a: Some("11".to_string()),
+                    b: None,
+                },
+                Example {
+                    input: "7, 8\n\"x\" & y\n".to_string(),
+                    a: None,
+                    b: Some("6,1".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn only_part_one() {
+        assert_eq!(
+            extract(&fixture("day-page-part1.html")),
+            vec![Example {
+                input: "2333133121414131402\n".to_string(),
+                a: Some("1928".to_string()),
+                b: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn part_two_reuses_the_example() {
+        let html = "<article><pre><code>1 2\n</code></pre><p><code><em>3</em></code></p></article>\
+                    <article><p>now <code><em>5</em></code></p></article>";
+
+        assert_eq!(
+            extract(html),
+            vec![Example {
+                input: "1 2\n".to_string(),
+                a: Some("3".to_string()),
+                b: Some("5".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn fetches_the_page() {
+        let (url, request) = stand_in_server(200, &fixture("day-page-part1.html"));
+        let conf = FetchConfig {
+            year: Some(2024),
+            agent: "tests".to_string(),
+            oauthkey: "cafe".to_string(),
+        };
+
+        let page = fetch_page(&url, &conf, 2024, 9).unwrap();
+        assert_eq!(extract(&page)[0].a.as_deref(), Some("1928"));
+
+        let request = request.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/9 "));
+        assert!(request.contains("session=cafe"));
+    }
+
+    #[test]
+    fn writes_goldens() {
+        let dir = env::temp_dir().join(format!("aoc-examples-{}", process::id()));
+        let examples = extract(&fixture("day-page.html"));
+
+        let written = write(&dir, 4, &examples, false).unwrap();
+        assert_eq!(written.len(), 4);
+        assert_eq!(
+            fs::read_to_string(dir.join("day4-1")).unwrap(),
+            "3   4\n4   3\n2 < 5\n"
+        );
+        let expected = parse_expected(&fs::read_to_string(dir.join("day4-2.expected")).unwrap());
+        assert_eq!(expected.unwrap().b.as_deref(), Some("6,1"));
+
+        assert!(write(&dir, 4, &examples, false).is_err());
+        assert!(write(&dir, 4, &examples, true).is_ok());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
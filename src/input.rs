@@ -0,0 +1,280 @@
+use crate::credentials;
+use crate::day::{self, FetchConfig};
+use crate::error::AocError;
+use crate::unlock;
+use chrono::Utc;
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// somewhere puzzle inputs come from
+pub trait InputSource: Send + Sync {
+    /// Ok(None) when this source does not have the day, so a Chain can ask the next one
+    fn get(&self, year: u32, day: u32) -> Result<Option<String>, AocError>;
+
+    /// keep an input another source had, Chain calls it on the sources it asked before
+    fn store(&self, _year: u32, _day: u32, _input: &str) -> Result<(), AocError> {
+        Ok(())
+    }
+
+    /// an error when store would fail, Chain checks it before asking the sources after this one
+    fn can_store(&self) -> Result<(), AocError> {
+        Ok(())
+    }
+}
+
+/// inputs kept in files as {root}/{year}/day{N}
+pub struct FileSource {
+    root: PathBuf,
+}
+
+impl FileSource {
+    pub fn new(root: impl Into<PathBuf>) -> FileSource {
+        FileSource { root: root.into() }
+    }
+
+    pub fn path(&self, year: u32, day: u32) -> PathBuf {
+        self.root.join(year.to_string()).join(format!("day{day}"))
+    }
+}
+
+impl InputSource for FileSource {
+    fn get(&self, year: u32, day: u32) -> Result<Option<String>, AocError> {
+        let file = self.path(year, day);
+        match fs::read_to_string(&file) {
+            Ok(input) => Ok(Some(input)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(AocError::io(file, err)),
+        }
+    }
+
+    fn store(&self, year: u32, day: u32, input: &str) -> Result<(), AocError> {
+        self.can_store()?;
+
+        let file = self.path(year, day);
+        let dir = file.parent().unwrap();
+        fs::create_dir_all(dir).map_err(|err| AocError::io(dir, err))?;
+        fs::write(&file, input).map_err(|err| AocError::io(&file, err))
+    }
+
+    fn can_store(&self) -> Result<(), AocError> {
+        // the root has to be made by hand so inputs do not end up wherever we happen to run
+        if self.root.exists() {
+            Ok(())
+        } else {
+            Err(AocError::Config(format!(
+                "no {} directory, please make it or cd to the path that has it",
+                self.root.display()
+            )))
+        }
+    }
+}
+
+/// inputs downloaded from {base_url}/{year}/day/{N}/input
+pub struct HttpSource {
+    base_url: String,
+    /// None to use the config from credentials
+    conf: Option<FetchConfig>,
+}
+
+impl HttpSource {
+    pub fn new(base_url: impl Into<String>) -> HttpSource {
+        HttpSource {
+            base_url: base_url.into(),
+            conf: None,
+        }
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn with_config(self, conf: FetchConfig) -> HttpSource {
+        HttpSource {
+            conf: Some(conf),
+            ..self
+        }
+    }
+}
+
+impl InputSource for HttpSource {
+    fn get(&self, year: u32, day: u32) -> Result<Option<String>, AocError> {
+        unlock::check_released(year, day, Utc::now())?;
+        let conf = match &self.conf {
+            Some(conf) => conf,
+            None => {
+                credentials::fetch_config("fetch inputs, or add the file to the inputs directory")?
+            }
+        };
+
+        eprintln!("fetching the input of day {day}");
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        day::fetch(&url, conf).map(Some)
+    }
+}
+
+/// inputs held in memory, for tests
+#[cfg_attr(not(test), allow(dead_code))]
+#[derive(Default)]
+pub struct MemorySource {
+    inputs: Mutex<HashMap<(u32, u32), String>>,
+}
+
+impl MemorySource {
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn with(self, year: u32, day: u32, input: impl Into<String>) -> MemorySource {
+        self.inputs
+            .lock()
+            .unwrap()
+            .insert((year, day), input.into());
+        self
+    }
+}
+
+impl InputSource for MemorySource {
+    fn get(&self, year: u32, day: u32) -> Result<Option<String>, AocError> {
+        Ok(self.inputs.lock().unwrap().get(&(year, day)).cloned())
+    }
+
+    fn store(&self, year: u32, day: u32, input: &str) -> Result<(), AocError> {
+        self.inputs
+            .lock()
+            .unwrap()
+            .insert((year, day), input.to_string());
+        Ok(())
+    }
+}
+
+/// asks its sources in order, what a later one has is stored in the ones before it
+pub struct Chain {
+    sources: Vec<Box<dyn InputSource>>,
+}
+
+impl Chain {
+    pub fn new(sources: Vec<Box<dyn InputSource>>) -> Chain {
+        Chain { sources }
+    }
+}
+
+impl InputSource for Chain {
+    fn get(&self, year: u32, day: u32) -> Result<Option<String>, AocError> {
+        for (i, source) in self.sources.iter().enumerate() {
+            // so nothing is fetched that could not be kept
+            for earlier in self.sources[..i].iter() {
+                earlier.can_store()?;
+            }
+
+            if let Some(input) = source.get(year, day)? {
+                for earlier in self.sources[..i].iter() {
+                    earlier.store(year, day, &input)?;
+                }
+                return Ok(Some(input));
+            }
+        }
+        Ok(None)
+    }
+
+    fn store(&self, year: u32, day: u32, input: &str) -> Result<(), AocError> {
+        self.sources
+            .iter()
+            .try_for_each(|source| source.store(year, day, input))
+    }
+
+    fn can_store(&self) -> Result<(), AocError> {
+        self.sources
+            .iter()
+            .try_for_each(|source| source.can_store())
+    }
+}
+
+/// the inputs directory, then adventofcode.com, saving what is fetched to the directory
+pub fn default_source() -> Chain {
+    Chain::new(vec![
+        Box::new(FileSource::new(day::INPUTS_DIR)),
+        Box::new(HttpSource::new(day::AOC_URL)),
+    ])
+}
+
+/// the input of the day from source, an error if it does not have it
+pub fn get_day_input(source: &dyn InputSource, year: u32, day: u32) -> Result<String, AocError> {
+    source.get(year, day)?.ok_or_else(|| {
+        AocError::Config(format!(
+            "no input for day {day} of {year}, add it to {}",
+            Path::new(day::INPUTS_DIR).display()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::stand_in_server;
+    use std::{env, process};
+
+    #[test]
+    fn chain_stores_what_it_fetches() {
+        let root = env::temp_dir().join(format!("aoc-input-{}", process::id()));
+        fs::create_dir_all(&root).unwrap();
+
+        let (url, request) = stand_in_server(200, "1 2\n3 4\n");
+        let conf = FetchConfig {
+            year: None,
+            agent: "tests".to_string(),
+            oauthkey: "cafe".to_string(),
+        };
+        let chain = Chain::new(vec![
+            Box::new(MemorySource::default()),
+            Box::new(FileSource::new(&root)),
+            Box::new(HttpSource::new(url).with_config(conf)),
+        ]);
+
+        assert_eq!(
+            get_day_input(&chain, 2024, 1).unwrap(),
+            "1 2\n3 4\n".to_string()
+        );
+        assert!(request
+            .join()
+            .unwrap()
+            .starts_with("GET /2024/day/1/input "));
+        assert_eq!(
+            fs::read_to_string(root.join("2024/day1")).unwrap(),
+            "1 2\n3 4\n"
+        );
+
+        // the server only answers once, so this has to come from the memory source
+        assert_eq!(
+            get_day_input(&chain, 2024, 1).unwrap(),
+            "1 2\n3 4\n".to_string()
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn missing_inputs() {
+        let memory = MemorySource::default().with(2024, 2, "x");
+
+        assert_eq!(memory.get(2024, 2).unwrap().as_deref(), Some("x"));
+        assert!(matches!(
+            get_day_input(&memory, 2024, 3),
+            Err(AocError::Config(_))
+        ));
+
+        let files = FileSource::new(env::temp_dir().join("aoc-input-missing"));
+        assert!(files.get(2024, 3).unwrap().is_none());
+        assert!(matches!(
+            files.store(2024, 3, "x"),
+            Err(AocError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn nothing_fetched_without_the_directory() {
+        let chain = Chain::new(vec![
+            Box::new(FileSource::new(env::temp_dir().join("aoc-input-missing"))),
+            Box::new(MemorySource::default().with(2024, 4, "x")),
+        ]);
+
+        assert!(matches!(chain.get(2024, 4), Err(AocError::Config(_))));
+    }
+}
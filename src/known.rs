@@ -0,0 +1,195 @@
+use crate::answer::Answer;
+use crate::day::Part;
+use crate::error::AocError;
+use crate::report::DayReport;
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// how a part's answer compares to the one recorded for it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Check {
+    Correct,
+    Regressed,
+    Unknown,
+}
+
+/// the confirmed answers for the real inputs of a year, kept in answers/{year}.toml as
+///
+/// [day1]
+/// a = 1889772
+/// b = "some text"
+pub struct KnownAnswers {
+    path: PathBuf,
+    days: BTreeMap<u32, [Option<toml::Value>; 2]>,
+}
+
+pub fn answers_path(year: u32) -> PathBuf {
+    PathBuf::from(format!("answers/{year}.toml"))
+}
+
+fn text(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+impl KnownAnswers {
+    /// the answers of year, none yet when the file does not exist
+    pub fn load(year: u32) -> Result<KnownAnswers, AocError> {
+        let path = answers_path(year);
+        match fs::read_to_string(&path) {
+            Ok(text) => KnownAnswers::parse(&path, &text),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(KnownAnswers {
+                path,
+                days: BTreeMap::new(),
+            }),
+            Err(err) => Err(AocError::io(&path, err)),
+        }
+    }
+
+    fn parse(path: &Path, text: &str) -> Result<KnownAnswers, AocError> {
+        let bad = |msg: String| AocError::Config(format!("{}: {msg}", path.display()));
+
+        let table =
+            toml::from_str::<toml::Table>(text).map_err(|err| bad(err.message().to_string()))?;
+
+        let mut days = BTreeMap::new();
+        for (key, value) in table {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse::<u32>().ok())
+                .filter(|day| (1..=25).contains(day))
+                .ok_or_else(|| bad(format!("unknown table [{key}], expected day1 to day25")))?;
+
+            let toml::Value::Table(parts) = value else {
+                return Err(bad(format!("{key} should be a table with a and b answers")));
+            };
+
+            let mut answers = [None, None];
+            for (part, answer) in parts {
+                match part.as_str() {
+                    "a" => answers[0] = Some(answer),
+                    "b" => answers[1] = Some(answer),
+                    _ => return Err(bad(format!("unknown part {key}.{part}, expected a or b"))),
+                }
+            }
+            days.insert(day, answers);
+        }
+
+        Ok(KnownAnswers {
+            path: path.to_path_buf(),
+            days,
+        })
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<String> {
+        self.days.get(&day)?[part as usize].as_ref().map(text)
+    }
+
+    pub fn check(&self, day: u32, part: Part, answer: &Answer) -> Check {
+        match self.get(day, part) {
+            Some(known) if known == answer.to_string() => Check::Correct,
+            Some(_) => Check::Regressed,
+            None => Check::Unknown,
+        }
+    }
+
+    /// checks every implemented answer of the report against the known ones
+    pub fn mark(&self, report: &mut DayReport) {
+        for (part, part_report) in [Part::A, Part::B].into_iter().zip(report.parts.iter_mut()) {
+            let Some(answer) = part_report.answer.as_ref().filter(|a| a.is_implemented()) else {
+                continue;
+            };
+
+            let check = self.check(report.day, part, answer);
+            if check == Check::Regressed {
+                part_report.expected = self.get(report.day, part);
+            }
+            part_report.check = Some(check);
+        }
+    }
+
+    /// remembers answer as the right one for the part, replacing what was there
+    pub fn record(&mut self, day: u32, part: Part, answer: &Answer) {
+        let value = match answer {
+            Answer::Int(n) => toml::Value::Integer(*n),
+            answer => toml::Value::String(answer.to_string()),
+        };
+        self.days.entry(day).or_default()[part as usize] = Some(value);
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        for (day, answers) in self.days.iter() {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out += &format!("[day{day}]\n");
+            for (part, answer) in ['a', 'b'].into_iter().zip(answers) {
+                if let Some(answer) = answer {
+                    out += &format!("{part} = {answer}\n");
+                }
+            }
+        }
+        out
+    }
+
+    pub fn save(&self) -> Result<(), AocError> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|err| AocError::io(dir, err))?;
+        }
+        fs::write(&self.path, self.render()).map_err(|err| AocError::io(&self.path, err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_answers() {
+        let known = KnownAnswers::parse(
+            Path::new("answers/2024.toml"),
+            "[day1]\na = 11\nb = 31\n\n[day14]\na = \"6,1\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(known.check(1, Part::A, &Answer::Int(11)), Check::Correct);
+        assert_eq!(known.check(1, Part::B, &Answer::Int(30)), Check::Regressed);
+        assert_eq!(
+            known.check(14, Part::A, &Answer::Coord(6, 1)),
+            Check::Correct
+        );
+        assert_eq!(known.check(14, Part::B, &Answer::Int(1)), Check::Unknown);
+        assert_eq!(known.check(2, Part::A, &Answer::Int(1)), Check::Unknown);
+
+        let err = KnownAnswers::parse(Path::new("answers/2024.toml"), "[day26]\na = 1\n");
+        assert!(matches!(err, Err(AocError::Config(_))));
+    }
+
+    #[test]
+    fn records_answers() {
+        let path = Path::new("answers/2024.toml");
+        let mut known = KnownAnswers::parse(path, "").unwrap();
+
+        known.record(10, Part::A, &Answer::Int(36));
+        known.record(2, Part::B, &Answer::Str("a \"quoted\" answer".to_string()));
+        known.record(10, Part::A, &Answer::Int(37));
+
+        let text = known.render();
+        assert_eq!(
+            text,
+            "[day2]\nb = 'a \"quoted\" answer'\n\n[day10]\na = 37\n"
+        );
+
+        let reloaded = KnownAnswers::parse(path, &text).unwrap();
+        assert_eq!(reloaded.get(2, Part::B).unwrap(), "a \"quoted\" answer");
+        assert_eq!(reloaded.get(10, Part::A).unwrap(), "37");
+    }
+}
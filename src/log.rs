@@ -0,0 +1,86 @@
+use colored::Colorize;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// shown with -v
+    Debug,
+    /// shown with -vv, for output that is too much to see every time
+    Trace,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Debug => write!(f, "debug"),
+            Level::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+/// where parsers and parts write diagnostics, the runner hands every day one
+/// that only shows what the -v flags on the command line ask for.
+/// it goes to stderr so json and csv output stay parseable
+#[derive(Clone, Copy, Debug)]
+pub struct Log {
+    day: u32,
+    shown: Option<Level>,
+}
+
+impl Log {
+    /// verbosity is how many times -v was given
+    pub fn new(day: u32, verbosity: u8) -> Log {
+        let shown = match verbosity {
+            0 => None,
+            1 => Some(Level::Debug),
+            _ => Some(Level::Trace),
+        };
+        Log { day, shown }
+    }
+
+    /// a log that shows nothing, for benchmarks
+    pub fn quiet() -> Log {
+        Log {
+            day: 0,
+            shown: None,
+        }
+    }
+
+    pub fn enabled(&self, level: Level) -> bool {
+        self.shown.is_some_and(|shown| level <= shown)
+    }
+
+    /// message is only built when it is going to be shown
+    pub fn log(&self, level: Level, message: impl FnOnce() -> String) {
+        if !self.enabled(level) {
+            return;
+        }
+
+        for line in message().lines() {
+            eprintln!("{} {line}", format!("[day{} {level}]", self.day).dimmed());
+        }
+    }
+
+    pub fn debug(&self, message: impl FnOnce() -> String) {
+        self.log(Level::Debug, message)
+    }
+
+    pub fn trace(&self, message: impl FnOnce() -> String) {
+        self.log(Level::Trace, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels() {
+        assert!(!Log::new(1, 0).enabled(Level::Debug));
+        assert!(Log::new(1, 1).enabled(Level::Debug));
+        assert!(!Log::new(1, 1).enabled(Level::Trace));
+        assert!(Log::new(1, 3).enabled(Level::Trace));
+
+        Log::quiet().debug(|| panic!("quiet logs do not build messages"));
+    }
+}
@@ -1,10 +1,36 @@
-use colored::Colorize;
-use std::{fs::File, io::Write};
+use std::{
+    path::{Path, PathBuf},
+    process,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use chrono::{self, FixedOffset, TimeZone, Utc};
+use chrono::{self, Utc};
 use clap::{Parser, Subcommand};
+use colored::Colorize;
 
+mod all;
+mod alloc;
+mod answer;
+mod bench;
+mod compare;
+mod credentials;
 mod day;
+mod error;
+mod examples;
+mod input;
+mod known;
+mod log;
+mod params;
+mod registry;
+mod repl;
+mod report;
+mod scaffold;
+mod select;
+mod submit;
+mod unlock;
+mod unwind;
+mod watch;
 
 mod day1;
 mod day10;
@@ -33,14 +59,27 @@ mod day8;
 mod day9;
 mod utils;
 
-use day::{create_day, FETCH_CONFIG};
+use answer::Answer;
+use bench::BenchOptions;
+use day::{DayRunner, FetchConfig, Part, RunOptions, AOC_URL};
+use error::AocError;
+use input::InputSource;
+use known::KnownAnswers;
 use once_cell::sync::Lazy;
+use registry::Registry;
+use report::{print_error as report, DayReport, Format, Printer, Summary};
+use select::{DaySelector, Parts};
 
 #[derive(Parser)]
 #[command(version,about,long_about = None)]
 struct Cli {
-    #[arg(short, long, value_name = "VERBOSE")]
-    verbose: bool,
+    /// show what days log while running, -vv for even more
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// puzzle year, defaults to the year in the fetch config or else the latest year with solutions
+    #[arg(long, short, global = true)]
+    year: Option<u32>,
 
     #[command(subcommand)]
     command: Commands,
@@ -49,122 +88,774 @@ struct Cli {
 #[derive(Subcommand, Clone)]
 enum Commands {
     Day {
-        #[arg(long, short)]
+        #[arg(long, short, value_parser = clap::value_parser!(u32).range(1..=25))]
         day: u32,
+
+        /// run only part a or b
+        #[arg(long, short, value_enum, default_value_t = Parts::Both)]
+        part: Parts,
+
+        /// override a puzzle constant of the day, can be repeated
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = params::parse_override)]
+        params: Vec<(String, i64)>,
+
+        /// run on this file instead of inputs/{year}/day{N}, use - to read stdin
+        #[arg(long, short)]
+        input: Option<PathBuf>,
+
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+
+        /// give up on a part after this many seconds and report it as timed out
+        #[arg(long, value_name = "SECONDS", value_parser = bench::parse_seconds)]
+        timeout: Option<Duration>,
+
+        /// if the puzzle is still locked, count down and fetch the input the moment it unlocks
+        #[arg(long, conflicts_with = "input")]
+        wait: bool,
     },
+    /// run the visualization of days that have one
     Exe {
+        /// days to run, like 1-5,9,12-
+        #[arg(long, short, visible_alias = "day", value_parser = select::parse_days)]
+        days: DaySelector,
+
+        /// override a puzzle constant of the day, can be repeated
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = params::parse_override)]
+        params: Vec<(String, i64)>,
+
+        /// run on this file instead of inputs/{year}/day{N}, use - to read stdin
+        #[arg(long, short)]
+        input: Option<PathBuf>,
+
+        /// options for the visualization, like -- --out images --steps 100, see -- --help
+        #[arg(last = true, value_name = "ARGS")]
+        args: Vec<String>,
+    },
+    All {
+        /// days to run instead of every released day, like 1-5,9,12-
+        #[arg(long, short, value_parser = select::parse_days)]
+        days: Option<DaySelector>,
+
+        /// run only part a or b
+        #[arg(long, short, value_enum, default_value_t = Parts::Both)]
+        part: Parts,
+
+        /// directory to read day{N} inputs from instead of inputs/{year}
+        #[arg(long, short, value_name = "DIR")]
+        input: Option<PathBuf>,
+
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+
+        /// run up to this many days at the same time, output is still in day order
+        #[arg(long, short, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        jobs: u32,
+
+        /// give up on a part after this many seconds and report it as timed out
+        #[arg(long, value_name = "SECONDS", value_parser = bench::parse_seconds)]
+        timeout: Option<Duration>,
+
+        /// write the answers and timings of this run to FILE, to --compare against later
+        #[arg(long, value_name = "FILE")]
+        save: Option<PathBuf>,
+
+        /// show how this run differs from one saved with --save (or --format json)
+        #[arg(long, value_name = "BASELINE")]
+        compare: Option<PathBuf>,
+
+        /// percent a phase may get slower than the baseline before it counts as a regression
+        #[arg(long, default_value_t = 10.0, requires = "compare")]
+        threshold: f64,
+    },
+    /// time each phase over many runs instead of once
+    Bench {
+        /// days to bench instead of every released day, like 1-5,9,12-
+        #[arg(long, short, visible_alias = "day", value_parser = select::parse_days)]
+        days: Option<DaySelector>,
+
+        /// run only part a or b
+        #[arg(long, short, value_enum, default_value_t = Parts::Both)]
+        part: Parts,
+
+        /// untimed runs before sampling
+        #[arg(long, default_value_t = 3)]
+        warmup: u32,
+
+        /// timed runs per phase
+        #[arg(long, short = 'n', default_value_t = 100, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+
+        /// stop sampling a day after this many seconds, split between parsing and the parts
+        #[arg(long, value_name = "SECONDS", value_parser = bench::parse_seconds)]
+        budget: Option<Duration>,
+
+        /// override a puzzle constant of the day, can be repeated
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = params::parse_override, requires = "days")]
+        params: Vec<(String, i64)>,
+
+        /// run on this file instead of inputs/{year}/day{N}, use - to read stdin
+        #[arg(long, short, requires = "days")]
+        input: Option<PathBuf>,
+    },
+    /// send an answer to adventofcode.com, by default the one the part computes
+    Submit {
+        #[arg(long, short, value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        #[arg(long, short, value_enum)]
+        part: Part,
+
+        /// submit this instead of running the part
+        #[arg(long, short)]
+        answer: Option<String>,
+
+        /// override a puzzle constant of the day, can be repeated
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = params::parse_override)]
+        params: Vec<(String, i64)>,
+
+        /// run on this file instead of inputs/{year}/day{N}, use - to read stdin
         #[arg(long, short)]
+        input: Option<PathBuf>,
+    },
+    /// rerun a day and its goldens whenever the input or a golden changes
+    Watch {
+        #[arg(long, short, value_parser = clap::value_parser!(u32).range(1..=25))]
         day: u32,
+
+        /// run only part a or b
+        #[arg(long, short, value_enum, default_value_t = Parts::Both)]
+        part: Parts,
+
+        /// override a puzzle constant of the day, can be repeated
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = params::parse_override)]
+        params: Vec<(String, i64)>,
+
+        /// watch this file instead of inputs/{year}/day{N}
+        #[arg(long, short)]
+        input: Option<PathBuf>,
+
+        /// give up on a part after this many seconds and report it as timed out
+        #[arg(long, value_name = "SECONDS", value_parser = bench::parse_seconds)]
+        timeout: Option<Duration>,
+
+        /// seconds between checks for changed files
+        #[arg(long, value_name = "SECONDS", default_value = "0.5", value_parser = bench::parse_seconds)]
+        interval: Duration,
     },
-    All,
+    /// parse a day once and run commands against it, type help for the list
+    Repl {
+        #[arg(long, short, value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        /// override a puzzle constant of the day, can be repeated
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = params::parse_override)]
+        params: Vec<(String, i64)>,
+
+        /// run on this file instead of inputs/{year}/day{N}
+        #[arg(long, short)]
+        input: Option<PathBuf>,
+    },
+    /// start a new day from templates/day.rs, run from the repository root
+    New {
+        #[arg(long, short, value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+    },
+    /// save the answers days give on their real input to answers/{year}.toml as the right ones
+    Record {
+        /// days to record, like 1-5,9,12-
+        #[arg(long, short, visible_alias = "day", value_parser = select::parse_days)]
+        days: DaySelector,
+
+        /// record only part a or b
+        #[arg(long, short, value_enum, default_value_t = Parts::Both)]
+        part: Parts,
+
+        /// replace answers that are already known
+        #[arg(long)]
+        force: bool,
+    },
+    /// write the examples on the puzzle page to goldens/{year}/day{N}-{k} with their answers
+    FetchExamples {
+        #[arg(long, short, value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        /// replace goldens that are already there
+        #[arg(long)]
+        force: bool,
+
+        /// if the puzzle is still locked, count down and fetch the page the moment it unlocks
+        #[arg(long)]
+        wait: bool,
+    },
+    /// save the session cookie to ~/.config/aoc/config.toml (or under XDG_CONFIG_HOME),
+    /// readable only by you. AOC_SESSION overrides it when set
     SetFetchConfig {
+        /// sent as the user agent, put a way to contact you in it
         #[arg(long, short)]
         agent: String,
 
+        /// the session cookie from adventofcode.com, without session=
         #[arg(long, short)]
         oauthkey: String,
-
-        #[arg(long, short)]
-        year: u64,
     },
+    /// show where the fetch config comes from and what is wrong with it
+    CheckConfig,
+}
+
+fn submit(
+    year: u32,
+    day: u32,
+    part: Part,
+    answer: Option<String>,
+    runner: DayRunner,
+    options: &RunOptions,
+) -> Result<(Answer, submit::Verdict), AocError> {
+    let conf = credentials::fetch_config("submit answers")?;
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let mut day_report = runner.run(day, options);
+            if let Some(err) = day_report.error.take() {
+                return Err(err);
+            }
+
+            match day_report.parts[part as usize].answer.take() {
+                Some(answer) if answer.is_implemented() => answer.to_string(),
+                _ => {
+                    return Err(AocError::Solve(format!(
+                        "part {part:?} of day {day} is not implemented"
+                    )))
+                }
+            }
+        }
+    };
+
+    println!("submitting {answer} for day {day} of {year} part {part:?}");
+
+    let verdict = submit::submit(AOC_URL, conf, year, day, part, &answer)?;
+    let answer = match answer.parse::<i64>() {
+        Ok(n) => Answer::Int(n),
+        Err(_) => Answer::Str(answer),
+    };
+    Ok((answer, verdict))
+}
+
+// known answers are only right for the real input with the default parameters
+fn check_answers(known: &KnownAnswers, options: &RunOptions, day_report: &mut DayReport) {
+    if options.input.is_none() && options.params.is_empty() {
+        known.mark(day_report);
+    }
+}
+
+fn record(
+    known: &mut KnownAnswers,
+    day: u32,
+    runner: DayRunner,
+    options: &RunOptions,
+    force: bool,
+) -> Result<(), AocError> {
+    let mut day_report = runner.run(day, options);
+    if let Some(err) = day_report.error.take() {
+        return Err(err);
+    }
+
+    for (part, part_report) in [Part::A, Part::B].into_iter().zip(day_report.parts) {
+        let Some(answer) = part_report.answer.filter(|answer| answer.is_implemented()) else {
+            continue;
+        };
+
+        match known.get(day, part) {
+            None => {
+                known.record(day, part, &answer);
+                println!("day {day} part {part}: recorded {answer}");
+            }
+            Some(old) if old == answer.to_string() => {
+                println!("day {day} part {part}: {answer} is already known");
+            }
+            Some(old) if force => {
+                known.record(day, part, &answer);
+                println!("day {day} part {part}: replaced {old} with {answer}");
+            }
+            Some(old) => println!(
+                "{}",
+                format!("day {day} part {part}: gives {answer} but {old} is known, use --force to replace it")
+                    .yellow()
+            ),
+        }
+    }
+    Ok(())
+}
+
+// an input file only makes sense for one day
+fn check_input(days: &DaySelector, input: &Option<PathBuf>) -> Result<(), AocError> {
+    if input.is_some() && days.single().is_none() {
+        return Err(AocError::Config(
+            "--input can only be used when a single day is selected".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// prints the error and ends the process with its exit code
+fn or_exit<T>(result: Result<T, AocError>) -> T {
+    result.unwrap_or_else(|err| {
+        report(&err);
+        process::exit(err.exit_code())
+    })
+}
+
+// only for the commands that have --wait
+fn wait_hint(err: Option<&AocError>) {
+    if let Some(AocError::Locked { .. }) = err {
+        eprintln!(
+            "{}",
+            "--wait counts down and fetches it once it unlocks".dimmed()
+        );
+    }
 }
 
 fn main() {
     let args = Cli::parse();
 
-    let days: [Box<dyn Fn(bool, u32, bool) -> Result<(), String>>; 25] = [
-        create_day(Lazy::force(&day1::DAY)),
-        create_day(Lazy::force(&day2::DAY)),
-        create_day(Lazy::force(&day3::DAY)),
-        create_day(Lazy::force(&day4::DAY)),
-        create_day(Lazy::force(&day5::DAY)),
-        create_day(Lazy::force(&day6::DAY)),
-        create_day(Lazy::force(&day7::DAY)),
-        create_day(Lazy::force(&day8::DAY)),
-        create_day(Lazy::force(&day9::DAY)),
-        create_day(Lazy::force(&day10::DAY)),
-        create_day(Lazy::force(&day11::DAY)),
-        create_day(Lazy::force(&day12::DAY)),
-        create_day(Lazy::force(&day13::DAY)),
-        create_day(Lazy::force(&day14::DAY)),
-        create_day(Lazy::force(&day15::DAY)),
-        create_day(Lazy::force(&day16::DAY)),
-        create_day(Lazy::force(&day17::DAY)),
-        create_day(Lazy::force(&day18::DAY)),
-        create_day(Lazy::force(&day19::DAY)),
-        create_day(Lazy::force(&day20::DAY)),
-        create_day(Lazy::force(&day21::DAY)),
-        create_day(Lazy::force(&day22::DAY)),
-        create_day(Lazy::force(&day23::DAY)),
-        create_day(Lazy::force(&day24::DAY)),
-        create_day(Lazy::force(&day25::DAY)),
-    ];
+    let registry = Registry::collect();
+
+    // --year, then the year from the fetch config, then the newest year we have solutions for
+    let year = args
+        .year
+        .or_else(credentials::default_year)
+        .or_else(|| registry.latest_year())
+        .unwrap();
 
     // let default_parallelism_approx = available_parallelism().unwrap().get();
 
     // println!("threads: {}", default_parallelism_approx);
     //
     rayon::ThreadPoolBuilder::new().build_global().unwrap();
+
+    let source: Arc<dyn InputSource> = Arc::new(input::default_source());
     // std::thread::sleep(time::Duration::from_millis(10));
 
     match args.command {
-        Commands::Day { day } => {
-            match (*days[(day - 1) as usize])(args.verbose, day, false) {
-                Ok(()) => {}
-                Err(err) => {
-                    println!("{}", format!("error: {err}").red())
-                }
+        Commands::Day {
+            day,
+            part,
+            params,
+            input,
+            format,
+            timeout,
+            wait,
+        } => {
+            let runner = or_exit(registry.get(year, day));
+            if wait {
+                unlock::wait_until_released(year, day);
+            }
+            let options = RunOptions {
+                year,
+                verbosity: args.verbose,
+                params,
+                input,
+                timeout,
+                parts: part,
+                source: source.clone(),
             };
+
+            let known = or_exit(KnownAnswers::load(year));
+            let mut printer = Printer::new(format);
+            let mut day_report = runner.run(day, &options);
+            check_answers(&known, &options, &mut day_report);
+            printer.print(&day_report);
+            printer.finish();
+            wait_hint(day_report.error.as_ref());
+
+            process::exit(day_report.exit_code());
         }
-        Commands::All => {
+        Commands::All {
+            days,
+            part,
+            input,
+            format,
+            jobs,
+            timeout,
+            save,
+            compare,
+            threshold,
+        } => {
+            if input.as_ref().is_some_and(|dir| dir.as_os_str() == "-") {
+                or_exit::<()>(Err(AocError::Config(
+                    "all can not read from stdin, pass a directory of day{N} files".to_string(),
+                )));
+            }
+
+            let known = or_exit(KnownAnswers::load(year));
+            let baseline = compare.map(|path| or_exit(compare::Baseline::load(&path)));
             let current_time = Utc::now();
+            let mut selected = Vec::new();
+            let days = days.unwrap_or_else(DaySelector::all);
             // let fetch_config = get_fetch_config();
-            for (day, dayfun) in days.iter().enumerate() {
-                let day = day + 1;
-                let runday = match Lazy::force(&FETCH_CONFIG) {
-                    Some(conf) => {
-                        let release_time = FixedOffset::west_opt(5 * 60 * 60)
-                            .unwrap()
-                            .with_ymd_and_hms(
-                                conf.year as i32,
-                                12,
-                                day as u32,
-                                0, // midnight in the utc time zone
-                                0,
-                                2,
-                            );
-
-                        current_time.naive_utc() > release_time.unwrap().naive_utc()
+            for (day, dayfun) in or_exit(days.resolve(&registry, year)) {
+                // with an input directory only the days that have a file in it are run
+                let day_input = input.as_ref().map(|dir| dir.join(format!("day{day}")));
+                if day_input.as_ref().is_some_and(|file| !file.exists()) {
+                    continue;
+                }
+
+                let runday = unlock::released(year, day, current_time);
+
+                let options = RunOptions {
+                    year,
+                    verbosity: args.verbose,
+                    params: Vec::new(),
+                    input: day_input,
+                    timeout,
+                    parts: part,
+                    source: source.clone(),
+                };
+
+                // explicit inputs do not need the puzzle to be released
+                if runday || options.input.is_some() {
+                    selected.push(all::Job {
+                        day,
+                        runner: dayfun,
+                        options,
+                    });
+                }
+            }
+
+            let mut exit_code = 0;
+            let mut printer = Printer::new(format);
+            let days = selected.len();
+            let mut failed = Vec::new();
+            let mut regressed = Vec::new();
+            let mut reports = Vec::new();
+            let (start, cpu_start) = (Instant::now(), all::cpu_time());
+
+            all::run_ordered(selected, jobs as usize, |mut day_report| {
+                if input.is_none() {
+                    known.mark(&mut day_report);
+                }
+                printer.print(&day_report);
+                if day_report.error.is_some() {
+                    failed.push(day_report.day);
+                }
+                if day_report.regressed() {
+                    regressed.push(day_report.day);
+                }
+                if exit_code == 0 {
+                    exit_code = day_report.exit_code();
+                }
+                reports.push(day_report);
+            });
+
+            printer.summary(&Summary {
+                days,
+                failed,
+                regressed,
+                wall: start.elapsed(),
+                cpu: cpu_start
+                    .zip(all::cpu_time())
+                    .map(|(start, end)| end - start),
+            });
+
+            if let Some(baseline) = baseline {
+                let comparison = compare::compare(&baseline, &reports, threshold / 100.0);
+                printer.summary(&comparison);
+                if exit_code == 0 && comparison.regressions() > 0 {
+                    exit_code = 1;
+                }
+            }
+            if let Some(path) = save {
+                or_exit(compare::save(&path, &reports));
+            }
+
+            printer.finish();
+            process::exit(exit_code);
+        }
+        Commands::Bench {
+            days,
+            part,
+            warmup,
+            iterations,
+            budget,
+            params,
+            input,
+        } => {
+            let current_time = Utc::now();
+            let mut exit_code = 0;
+
+            let selected = match days {
+                Some(days) => {
+                    or_exit(check_input(&days, &input));
+                    or_exit(days.resolve(&registry, year))
+                }
+                None => registry
+                    .days(year)
+                    .filter(|(day, _)| unlock::released(year, *day, current_time))
+                    .collect(),
+            };
+
+            let bench = BenchOptions {
+                warmup,
+                iterations,
+                budget,
+            };
+
+            for (day, runner) in selected {
+                let options = RunOptions {
+                    year,
+                    verbosity: args.verbose,
+                    params: params.clone(),
+                    input: input.clone(),
+                    timeout: None,
+                    parts: part,
+                    source: source.clone(),
+                };
+
+                if let Err(err) = runner.bench(day, &options, &bench) {
+                    report(&err);
+                    if exit_code == 0 {
+                        exit_code = err.exit_code();
                     }
-                    None => true,
+                }
+            }
+
+            process::exit(exit_code);
+        }
+        Commands::Submit {
+            day,
+            part,
+            answer,
+            params,
+            input,
+        } => {
+            let runner = or_exit(registry.get(year, day));
+            let options = RunOptions {
+                year,
+                verbosity: args.verbose,
+                params,
+                input,
+                timeout: None,
+                parts: Parts::Both,
+                source: source.clone(),
+            };
+
+            let (answer, verdict) = or_exit(submit(year, day, part, answer, runner, &options));
+            println!("{verdict}");
+            if !verdict.is_correct() {
+                process::exit(1);
+            }
+
+            // a correct answer is right for the real input whatever was run to get it
+            let mut known = or_exit(KnownAnswers::load(year));
+            known.record(day, part, &answer);
+            or_exit(known.save());
+        }
+        Commands::Repl { day, params, input } => {
+            if input.as_ref().is_some_and(|file| file.as_os_str() == "-") {
+                or_exit::<()>(Err(AocError::Config(
+                    "repl reads its commands from stdin, the input has to be a file".to_string(),
+                )));
+            }
+
+            let runner = or_exit(registry.get(year, day));
+            let options = RunOptions {
+                year,
+                verbosity: args.verbose,
+                params,
+                input,
+                timeout: None,
+                parts: Parts::Both,
+                source: source.clone(),
+            };
+
+            or_exit(runner.repl(day, &options));
+        }
+        Commands::New { day } => {
+            if registry.get(year, day).is_ok() {
+                or_exit::<()>(Err(AocError::Config(format!(
+                    "day {day} of {year} already has a solution"
+                ))));
+            }
+
+            for path in or_exit(scaffold::new_day(Path::new("."), year, day)) {
+                println!("wrote {}", path.display());
+            }
+            println!(
+                "put the example in {}/day{day} and rebuild to run it",
+                day::goldens_dir(year).display()
+            );
+        }
+        Commands::Record { days, part, force } => {
+            let mut known = or_exit(KnownAnswers::load(year));
+            let mut exit_code = 0;
+
+            for (day, runner) in or_exit(days.resolve(&registry, year)) {
+                let options = RunOptions {
+                    year,
+                    verbosity: args.verbose,
+                    params: Vec::new(),
+                    input: None,
+                    timeout: None,
+                    parts: part,
+                    source: source.clone(),
                 };
 
-                if runday {
-                    match (*dayfun)(args.verbose, day as u32, false) {
-                        Ok(()) => {}
-                        Err(err) => {
-                            println!("{}", format!("error: {err}").red())
-                        }
+                if let Err(err) = record(&mut known, day, runner, &options, force) {
+                    println!("day {day}:");
+                    report(&err);
+                    if exit_code == 0 {
+                        exit_code = err.exit_code();
                     }
                 }
             }
+
+            or_exit(known.save());
+            process::exit(exit_code);
         }
-        Commands::SetFetchConfig {
-            agent,
-            oauthkey,
-            year,
+        Commands::Watch {
+            day,
+            part,
+            params,
+            input,
+            timeout,
+            interval,
         } => {
-            let mut file = File::create("AOC_FETCH_CONFIG").unwrap();
-            file.write_all(format!("{agent}\n{oauthkey}\n{year}").as_bytes())
-                .unwrap();
+            if input.as_ref().is_some_and(|file| file.as_os_str() == "-") {
+                or_exit::<()>(Err(AocError::Config(
+                    "watch can not read from stdin, it needs a file to watch".to_string(),
+                )));
+            }
+
+            let runner = or_exit(registry.get(year, day));
+            let options = RunOptions {
+                year,
+                verbosity: args.verbose,
+                params,
+                input,
+                timeout,
+                parts: part,
+                source: source.clone(),
+            };
+
+            watch::watch(day, runner, &options, interval);
         }
-        Commands::Exe { day } => {
-            match (*days[(day - 1) as usize])(args.verbose, day, true) {
-                Ok(()) => {}
+        Commands::FetchExamples { day, force, wait } => {
+            let conf = or_exit(credentials::fetch_config("fetch examples"));
+            if wait {
+                unlock::wait_until_released(year, day);
+            }
+
+            let page = match examples::fetch_page(AOC_URL, conf, year, day) {
+                Ok(page) => page,
                 Err(err) => {
-                    println!("{}", format!("error: {err}").red())
+                    report(&err);
+                    wait_hint(Some(&err));
+                    process::exit(err.exit_code());
                 }
             };
+            let found = examples::extract(&page);
+            if found.is_empty() {
+                eprintln!("{}", format!("no examples on the page of day {day}").red());
+                process::exit(1);
+            }
+
+            for path in or_exit(examples::write(&day::goldens_dir(year), day, &found, force)) {
+                println!("wrote {}", path.display());
+            }
+        }
+        Commands::SetFetchConfig { agent, oauthkey } => {
+            let problems = credentials::session_problems(&oauthkey);
+            if !problems.is_empty() {
+                or_exit::<()>(Err(AocError::Config(problems.join(", "))));
+            }
+            for warning in credentials::session_warnings(&oauthkey) {
+                eprintln!("{}", format!("warning: {warning}").yellow());
+            }
+
+            let Some(path) = credentials::xdg_path(|name| std::env::var(name).ok()) else {
+                or_exit(Err(AocError::Config(
+                    "neither XDG_CONFIG_HOME nor HOME is set, there is nowhere to put the config"
+                        .to_string(),
+                )))
+            };
+            let conf = FetchConfig {
+                year: args.year,
+                agent,
+                oauthkey,
+            };
+            or_exit(credentials::save(&path, &conf));
+            println!("wrote {}", path.display());
+
+            if Path::new(credentials::LEGACY_PATH).exists() {
+                println!(
+                    "{}",
+                    format!(
+                        "{} is not needed anymore and has the cookie in it, delete it",
+                        credentials::LEGACY_PATH
+                    )
+                    .yellow()
+                );
+            }
+        }
+        Commands::CheckConfig => {
+            let found = Lazy::force(&credentials::FETCH_CONFIGS);
+            if found.is_empty() {
+                or_exit::<()>(Err(AocError::Config(
+                    "no fetch config, set AOC_SESSION or run set-fetch-config".to_string(),
+                )));
+            }
+
+            for (i, found) in found.iter().enumerate() {
+                if i == 0 {
+                    println!("using {}", found.source);
+                } else {
+                    println!(
+                        "{}",
+                        format!("also set, not used: {}", found.source).dimmed()
+                    );
+                }
+                match &found.config {
+                    Ok(conf) => {
+                        let year = conf.year.map_or("not set".to_string(), |y| y.to_string());
+                        println!("  {} agent {:?}, year {year}", "✓".green(), conf.agent);
+                    }
+                    Err(problems) => {
+                        for problem in problems {
+                            println!("  {} {problem}", "✗".red());
+                        }
+                    }
+                }
+                for warning in found.warnings.iter() {
+                    println!("  {} {warning}", "!".yellow());
+                }
+            }
+
+            if let Err(err) = credentials::fetch_config("fetch") {
+                process::exit(err.exit_code());
+            }
+        }
+        Commands::Exe {
+            days,
+            params,
+            input,
+            args: exe_args,
+        } => {
+            or_exit(check_input(&days, &input));
+
+            for (day, runner) in or_exit(days.resolve(&registry, year)) {
+                let options = RunOptions {
+                    year,
+                    verbosity: args.verbose,
+                    params: params.clone(),
+                    input: input.clone(),
+                    timeout: None,
+                    parts: Parts::Both,
+                    source: source.clone(),
+                };
+
+                or_exit(runner.exe(day, &options, &exe_args));
+            }
         }
     }
 }
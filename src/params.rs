@@ -0,0 +1,151 @@
+use crate::error::AocError;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+/// a named puzzle constant that can be changed with --param name=value,
+/// so example inputs (with smaller grids, fewer steps, ...) can go through the normal runner
+pub struct Param {
+    pub name: &'static str,
+    pub default: i64,
+    /// the smallest and largest values the day can run with
+    pub min: i64,
+    pub max: i64,
+}
+
+pub struct Params {
+    values: HashMap<&'static str, i64>,
+}
+
+impl Params {
+    pub fn new(decls: &[Param], overrides: &[(String, i64)]) -> Result<Params, AocError> {
+        let mut values: HashMap<&'static str, i64> =
+            decls.iter().map(|p| (p.name, p.default)).collect();
+
+        for (name, value) in overrides {
+            match values.get_mut(name.as_str()) {
+                Some(slot) => {
+                    let decl = decls.iter().find(|p| p.name == name).unwrap();
+                    if !(decl.min..=decl.max).contains(value) {
+                        return Err(AocError::Config(format!(
+                            "{name} has to be between {} and {}, got {value}",
+                            decl.min, decl.max
+                        )));
+                    }
+                    *slot = *value
+                }
+                None if decls.is_empty() => {
+                    return Err(AocError::Config(format!(
+                        "unknown parameter {name:?}, this day has no parameters"
+                    )))
+                }
+                None => {
+                    return Err(AocError::Config(format!(
+                        "unknown parameter {name:?}, available parameters: {}",
+                        decls
+                            .iter()
+                            .map(|p| format!("{} (default {})", p.name, p.default))
+                            .join(", ")
+                    )))
+                }
+            }
+        }
+
+        Ok(Params { values })
+    }
+
+    pub fn get(&self, name: &str) -> i64 {
+        match self.values.get(name) {
+            Some(value) => *value,
+            None => panic!("parameter {name:?} was used but never declared"),
+        }
+    }
+
+    /// the parameter converted to the type the day uses, an error if it does not fit
+    pub fn get_as<T: TryFrom<i64>>(&self, name: &str) -> Result<T, AocError> {
+        let value = self.get(name);
+        T::try_from(value)
+            .map_err(|_| AocError::Config(format!("{name} = {value} is out of range for this day")))
+    }
+}
+
+/// clap value parser for name=value
+pub fn parse_override(arg: &str) -> Result<(String, i64), String> {
+    let (name, value) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected name=value, got {arg:?}"))?;
+    let value = value
+        .parse::<i64>()
+        .map_err(|err| format!("invalid value for {name}: {err}"))?;
+
+    Ok((name.to_string(), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECLS: &[Param] = &[
+        Param {
+            name: "size",
+            default: 70,
+            min: 1,
+            max: 1000,
+        },
+        Param {
+            name: "steps",
+            default: 1024,
+            min: 0,
+            max: i64::MAX,
+        },
+    ];
+
+    #[test]
+    fn defaults_and_overrides() {
+        let params = Params::new(DECLS, &[]).unwrap();
+        assert_eq!(params.get("size"), 70);
+        assert_eq!(params.get("steps"), 1024);
+
+        let params = Params::new(DECLS, &[("size".to_string(), 6)]).unwrap();
+        assert_eq!(params.get("size"), 6);
+        assert_eq!(params.get("steps"), 1024);
+    }
+
+    #[test]
+    fn unknown_names() {
+        let err = Params::new(DECLS, &[("width".to_string(), 6)])
+            .err()
+            .unwrap();
+        assert!(matches!(err, AocError::Config(_)));
+        assert_eq!(err.exit_code(), 5);
+
+        assert!(matches!(
+            Params::new(&[], &[("size".to_string(), 6)]),
+            Err(AocError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn ranges() {
+        assert!(Params::new(DECLS, &[("size".to_string(), 1000)]).is_ok());
+        for size in [0, -1, 1001] {
+            let err = Params::new(DECLS, &[("size".to_string(), size)])
+                .err()
+                .unwrap();
+            assert!(matches!(err, AocError::Config(_)));
+        }
+
+        let params = Params::new(DECLS, &[("steps".to_string(), 1 << 40)]).unwrap();
+        assert_eq!(params.get_as::<usize>("size").unwrap(), 70);
+        assert!(matches!(
+            params.get_as::<u32>("steps"),
+            Err(AocError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn overrides() {
+        assert_eq!(parse_override("size=-3"), Ok(("size".to_string(), -3)));
+        assert!(parse_override("size").is_err());
+        assert!(parse_override("size=big").is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::day::DayRunner;
+use crate::error::AocError;
+use std::collections::BTreeMap;
+
+/// what register! submits for a day, collected into the registry at startup
+pub struct Registration {
+    pub year: u32,
+    pub day: u32,
+    pub solution: DayRunner,
+}
+
+inventory::collect!(Registration);
+
+/// makes a Solution known to the runner as the given year and day, put it next to the impl:
+/// `registry::register!(2024, 1, Day1);`
+macro_rules! register {
+    ($year:literal, $day:literal, $solution:expr) => {
+        inventory::submit! {
+            $crate::registry::Registration {
+                year: $year,
+                day: $day,
+                solution: &$solution,
+            }
+        }
+    };
+}
+
+pub(crate) use register;
+
+/// every solution we have, keyed by (year, day)
+pub struct Registry {
+    days: BTreeMap<(u32, u32), DayRunner>,
+}
+
+impl Registry {
+    /// all the days registered with register!, panics if a day was registered twice
+    pub fn collect() -> Registry {
+        Registry::from_registrations(inventory::iter::<Registration>)
+    }
+
+    fn from_registrations<'a>(regs: impl IntoIterator<Item = &'a Registration>) -> Registry {
+        let mut days = BTreeMap::new();
+
+        for reg in regs {
+            if days.insert((reg.year, reg.day), reg.solution).is_some() {
+                panic!("day {} of {} is registered twice", reg.day, reg.year);
+            }
+        }
+
+        Registry { days }
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> Result<DayRunner, AocError> {
+        self.days.get(&(year, day)).copied().ok_or_else(|| {
+            AocError::Config(format!("there is no solution for day {day} of {year}"))
+        })
+    }
+
+    /// the registered days of a year in order
+    pub fn days(&self, year: u32) -> impl Iterator<Item = (u32, DayRunner)> + '_ {
+        self.days
+            .range((year, 0)..=(year, u32::MAX))
+            .map(|((_, day), runner)| (*day, *runner))
+    }
+
+    pub fn latest_year(&self) -> Option<u32> {
+        self.days.keys().next_back().map(|(year, _)| *year)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day1::Day1;
+
+    // which solution it is does not matter here
+    fn reg(year: u32, day: u32) -> Registration {
+        Registration {
+            year,
+            day,
+            solution: &Day1,
+        }
+    }
+
+    #[test]
+    fn days_in_order() {
+        let regs = [reg(2024, 10), reg(2023, 1), reg(2024, 2), reg(2024, 1)];
+        let registry = Registry::from_registrations(&regs);
+
+        assert_eq!(
+            registry.days(2024).map(|(day, _)| day).collect::<Vec<_>>(),
+            vec![1, 2, 10]
+        );
+        assert_eq!(registry.latest_year(), Some(2024));
+        assert!(registry.get(2023, 1).is_ok());
+        assert!(matches!(registry.get(2023, 2), Err(AocError::Config(_))));
+    }
+
+    #[test]
+    fn latest_year() {
+        let regs = [reg(2022, 5), reg(2025, 1), reg(2023, 25)];
+        assert_eq!(
+            Registry::from_registrations(&regs).latest_year(),
+            Some(2025)
+        );
+        assert_eq!(Registry::from_registrations(&[]).latest_year(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "day 3 of 2024 is registered twice")]
+    fn registered_twice() {
+        Registry::from_registrations(&[reg(2024, 3), reg(2024, 3)]);
+    }
+
+    #[test]
+    fn every_day_registered_once() {
+        assert_eq!(Registry::collect().days(2024).count(), 25);
+    }
+}
@@ -0,0 +1,210 @@
+use crate::bench::{self, BenchOptions};
+use crate::day::{self, Part, RunOptions, Solution};
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::{self, Params};
+use crate::report::print_error;
+use crate::unwind;
+use colored::Colorize;
+use std::{
+    io::{self, BufRead, Write},
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Part(Part),
+    Time(Part),
+    Exe(Vec<String>),
+    Set(String, i64),
+    Params,
+    Reload,
+    ShowInput,
+    Help,
+    Quit,
+}
+
+const HELP: &str = "\
+a, b             run a part
+time a, time b   bench a part
+exe [args]       run the day's visualization
+set name=value   change a parameter
+params           show the parameters
+reload           read and parse the input again
+show input       print the parsed input
+quit             leave, so does ctrl-d";
+
+/// None for an empty line
+pub fn parse_command(line: &str) -> Result<Option<Command>, String> {
+    let words = line.split_whitespace().collect::<Vec<_>>();
+
+    let part = |word: &str| match word {
+        "a" => Ok(Part::A),
+        "b" => Ok(Part::B),
+        _ => Err(format!("there is no part {word:?}, only a and b")),
+    };
+
+    let command = match words.as_slice() {
+        [] => return Ok(None),
+        [which @ ("a" | "b")] => Command::Part(part(which)?),
+        ["time", which] => Command::Time(part(which)?),
+        ["exe", args @ ..] => Command::Exe(args.iter().map(|arg| arg.to_string()).collect()),
+        ["set", arg] => {
+            let (name, value) = params::parse_override(arg)?;
+            Command::Set(name, value)
+        }
+        ["params"] => Command::Params,
+        ["reload"] => Command::Reload,
+        ["show", "input"] => Command::ShowInput,
+        ["help"] => Command::Help,
+        ["quit" | "exit"] => Command::Quit,
+        _ => return Err(format!("unknown command {:?}, try help", line.trim())),
+    };
+    Ok(Some(command))
+}
+
+fn load<S: Solution>(
+    day: &S,
+    number: u32,
+    options: &RunOptions,
+    log: &Log,
+) -> Result<S::Input, AocError> {
+    let file = day::read_input(number, options)?;
+
+    let now = Instant::now();
+    let parsed = unwind::catch(|| day.parse(file, log))??;
+    println!("parsing time: {:.2?}", now.elapsed());
+
+    Ok(parsed)
+}
+
+/// keeps the parsed input of a day around and runs commands against it from stdin
+pub fn repl_day<S: Solution>(day: &S, options: &RunOptions, number: u32) -> Result<(), AocError> {
+    let log = Log::new(number, options.verbosity);
+    let mut overrides = options.params.clone();
+    let mut params = Params::new(&day.params(), &overrides)?;
+    let mut input = load(day, number, options, &log)?;
+
+    println!("{}", "type help for the commands".dimmed());
+
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        print!("day {number}> ");
+        io::stdout()
+            .flush()
+            .map_err(|err| AocError::io("<stdout>", err))?;
+
+        let Some(line) = lines.next() else {
+            println!();
+            return Ok(());
+        };
+        let line = line.map_err(|err| AocError::io("<stdin>", err))?;
+
+        let command = match parse_command(&line) {
+            Ok(Some(command)) => command,
+            Ok(None) => continue,
+            Err(msg) => {
+                println!("{}", msg.red());
+                continue;
+            }
+        };
+
+        match command {
+            Command::Part(which) => {
+                let (_, solve) = day::parts::<S>()[which as usize];
+                let solved = day::solve_measured(day, solve, &input, &params, &log);
+                match solved.result {
+                    Ok(answer) => {
+                        println!("{answer}");
+                        println!("part {which} time: {:.2?}", solved.time);
+                    }
+                    Err(err) => print_error(&err),
+                }
+            }
+            Command::Time(which) => {
+                let (_, solve) = day::parts::<S>()[which as usize];
+                let bench = BenchOptions {
+                    warmup: 3,
+                    iterations: 100,
+                    budget: Some(Duration::from_secs(5)),
+                };
+                let quiet = Log::quiet();
+                match bench::sample(&bench, 1, || {
+                    unwind::catch(|| solve(day, &input, &params, &quiet))?
+                }) {
+                    Ok((_, stats)) => println!("part {which} {stats}"),
+                    Err(err) => print_error(&err),
+                }
+            }
+            Command::Exe(args) => match unwind::catch(|| day.visualize(&input, &params, &args)) {
+                Ok(Some(Ok(()))) => {}
+                Ok(None) => println!("no visualization for this day"),
+                Ok(Some(Err(err))) | Err(err) => print_error(&err),
+            },
+            Command::Set(name, value) => {
+                let mut changed = overrides.clone();
+                changed.retain(|(other, _)| *other != name);
+                changed.push((name, value));
+
+                match Params::new(&day.params(), &changed) {
+                    Ok(new) => (params, overrides) = (new, changed),
+                    Err(err) => print_error(&err),
+                }
+            }
+            Command::Params => {
+                let decls = day.params();
+                if decls.is_empty() {
+                    println!("this day has no parameters");
+                }
+                for param in decls {
+                    println!(
+                        "{} = {} (default {}, {} to {})",
+                        param.name,
+                        params.get(param.name),
+                        param.default,
+                        param.min,
+                        param.max
+                    );
+                }
+            }
+            Command::Reload => match load(day, number, options, &log) {
+                Ok(reloaded) => input = reloaded,
+                Err(err) => {
+                    print_error(&err);
+                    println!("{}", "keeping the input from before".yellow());
+                }
+            },
+            Command::ShowInput => println!("{input:#?}"),
+            Command::Help => println!("{HELP}"),
+            Command::Quit => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands() {
+        assert_eq!(parse_command("  "), Ok(None));
+        assert_eq!(parse_command("b"), Ok(Some(Command::Part(Part::B))));
+        assert_eq!(parse_command("time  a"), Ok(Some(Command::Time(Part::A))));
+        assert_eq!(
+            parse_command("set size=7"),
+            Ok(Some(Command::Set("size".to_string(), 7)))
+        );
+        assert_eq!(parse_command("show input"), Ok(Some(Command::ShowInput)));
+        assert_eq!(
+            parse_command("exe --steps 5"),
+            Ok(Some(Command::Exe(vec![
+                "--steps".to_string(),
+                "5".to_string()
+            ])))
+        );
+
+        assert!(parse_command("time c").is_err());
+        assert!(parse_command("set size").is_err());
+        assert!(parse_command("run").is_err());
+    }
+}
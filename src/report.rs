@@ -0,0 +1,453 @@
+use crate::alloc::AllocStats;
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::known::Check;
+use clap::ValueEnum;
+use colored::Colorize;
+use core::fmt;
+use itertools::Itertools;
+use serde::{Serialize, Serializer};
+use std::{error::Error, time::Duration};
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+    NotImplemented,
+    Failed,
+    TimedOut,
+    /// never ran because an earlier phase of the day failed
+    Skipped,
+}
+
+#[derive(Serialize)]
+pub struct PartReport {
+    pub part: char,
+    pub status: Status,
+    pub answer: Option<Answer>,
+    /// compared to answers/{year}.toml, only for runs on the real input
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check: Option<Check>,
+    /// the known answer when this one is different
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    #[serde(rename = "time_ns", serialize_with = "nanos")]
+    pub time: Option<Duration>,
+    /// only measured with the alloc-stats feature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<AllocStats>,
+}
+
+/// everything a single run of a day produced, errors included, so that it can be
+/// printed for people or written out as json/csv for other tools
+#[derive(Serialize)]
+pub struct DayReport {
+    pub year: u32,
+    pub day: u32,
+    #[serde(rename = "parse_time_ns", serialize_with = "nanos")]
+    pub parse_time: Option<Duration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_alloc: Option<AllocStats>,
+    pub parts: [PartReport; 2],
+    #[serde(serialize_with = "message")]
+    pub error: Option<AocError>,
+}
+
+impl PartReport {
+    fn new(part: char) -> PartReport {
+        PartReport {
+            part,
+            status: Status::Skipped,
+            answer: None,
+            check: None,
+            expected: None,
+            time: None,
+            alloc: None,
+        }
+    }
+}
+
+impl DayReport {
+    pub fn new(year: u32, day: u32) -> DayReport {
+        DayReport {
+            year,
+            day,
+            parse_time: None,
+            parse_alloc: None,
+            parts: [PartReport::new('a'), PartReport::new('b')],
+            error: None,
+        }
+    }
+
+    pub fn regressed(&self) -> bool {
+        self.parts
+            .iter()
+            .any(|part| part.check == Some(Check::Regressed))
+    }
+
+    /// 1 for a wrong answer, like a rejected submit
+    pub fn exit_code(&self) -> i32 {
+        match &self.error {
+            Some(err) => err.exit_code(),
+            None if self.regressed() => 1,
+            None => 0,
+        }
+    }
+}
+
+fn nanos<S: Serializer>(time: &Option<Duration>, s: S) -> Result<S::Ok, S::Error> {
+    match time {
+        Some(time) => s.serialize_u128(time.as_nanos()),
+        None => s.serialize_none(),
+    }
+}
+
+fn message<S: Serializer>(err: &Option<AocError>, s: S) -> Result<S::Ok, S::Error> {
+    match err {
+        Some(err) => s.serialize_str(&error_chain(err)),
+        None => s.serialize_none(),
+    }
+}
+
+/// the error and all of its causes on one line
+fn error_chain(err: &AocError) -> String {
+    let mut msg = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        msg += &format!(": {cause}");
+        source = cause.source();
+    }
+    msg
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Int(n) => s.serialize_i64(*n),
+            Answer::BigInt(n) => s.serialize_str(&n.to_string()),
+            Answer::Str(str) => s.serialize_str(str),
+            Answer::Coord(x, y) => [x, y].serialize(s),
+            Answer::NotImplemented => s.serialize_none(),
+        }
+    }
+}
+
+pub fn print_error(err: &AocError) {
+    println!("{}", format!("error: {err}").red());
+
+    let mut source = err.source();
+    while let Some(cause) = source {
+        println!("{}", format!("  caused by: {cause}").red());
+        source = cause.source();
+    }
+}
+
+fn print_text(report: &DayReport) {
+    println!(
+        "{}",
+        format!("======= Day {:2} ========", report.day).bright_red()
+    );
+
+    if let Some(time) = report.parse_time {
+        println!("parsing time: {:.2?}", time);
+        if let Some(alloc) = report.parse_alloc {
+            println!("parsing memory: {alloc}");
+        }
+
+        for part in report.parts.iter() {
+            if part.status == Status::Skipped {
+                continue;
+            }
+
+            println!(
+                "{}",
+                format!("====== part {} ======", part.part.to_ascii_uppercase()).bright_magenta()
+            );
+
+            match (&part.answer, part.time) {
+                (Some(answer), _) if !answer.is_implemented() => println!("{}", answer),
+                (Some(answer), Some(time)) => {
+                    let check = match (part.check, &part.expected) {
+                        (Some(Check::Correct), _) => format!(" {}", "✓".green()),
+                        (Some(Check::Regressed), Some(expected)) => {
+                            format!(" {}", format!("✗ (expected {expected})").red())
+                        }
+                        (Some(Check::Regressed), None) => format!(" {}", "✗".red()),
+                        (Some(Check::Unknown), _) => format!(" {}", "?".yellow()),
+                        (None, _) => String::new(),
+                    };
+                    if answer.is_plausible() {
+                        println!("{}{check}", answer);
+                    } else {
+                        println!("{}{check} {}", answer, "(suspicious answer)".yellow());
+                    }
+                    println!("part {} time: {:.2?}", part.part, time);
+                    if let Some(alloc) = part.alloc {
+                        println!("part {} memory: {alloc}", part.part);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if let Some(err) = &report.error {
+        print_error(err);
+    }
+}
+
+/// totals for a run of several days
+pub struct Summary {
+    pub days: usize,
+    /// days that ended with an error (including panics and timeouts)
+    pub failed: Vec<u32>,
+    /// days that gave a different answer than the known one
+    pub regressed: Vec<u32>,
+    pub wall: Duration,
+    /// None where the platform can not tell us
+    pub cpu: Option<Duration>,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", "======= Summary ========".bright_red())?;
+        let bad = self
+            .failed
+            .iter()
+            .chain(self.regressed.iter())
+            .unique()
+            .count();
+        writeln!(f, "passed: {} of {} days", self.days - bad, self.days)?;
+        if !self.failed.is_empty() {
+            let days = self.failed.iter().map(|day| day.to_string()).join(", ");
+            writeln!(f, "{}", format!("failed: day {days}").red())?;
+        }
+        if !self.regressed.is_empty() {
+            let days = self.regressed.iter().map(|day| day.to_string()).join(", ");
+            writeln!(f, "{}", format!("wrong answers: day {days}").red())?;
+        }
+        write!(f, "wall time: {:.2?}", self.wall)?;
+        if let Some(cpu) = self.cpu {
+            write!(f, "\ncpu time: {:.2?}", cpu)?;
+        }
+        Ok(())
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+const CSV_HEADER: &str =
+    "year,day,phase,status,answer,check,time_ns,allocations,bytes,peak_bytes,error";
+
+fn csv_name<T: Serialize>(value: T) -> String {
+    serde_json::to_value(value)
+        .unwrap()
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+/// the parse, a and b rows of a day
+fn csv_rows(report: &DayReport) -> Vec<String> {
+    let error = report.error.as_ref().map(error_chain).unwrap_or_default();
+    let parse_status = match (report.parse_time, &report.error) {
+        (Some(_), _) => Status::Ok,
+        (None, Some(_)) => Status::Failed,
+        (None, None) => Status::Skipped,
+    };
+    let time = |time: Option<Duration>| time.map(|t| t.as_nanos().to_string());
+    let alloc = |alloc: Option<AllocStats>| match alloc {
+        Some(a) => format!("{},{},{}", a.allocations, a.bytes, a.peak_bytes),
+        None => ",,".to_string(),
+    };
+
+    let mut rows = vec![format!(
+        "{},{},parse,{},,,{},{},{}",
+        report.year,
+        report.day,
+        csv_name(parse_status),
+        time(report.parse_time).unwrap_or_default(),
+        alloc(report.parse_alloc),
+        if parse_status == Status::Failed {
+            csv_field(&error)
+        } else {
+            String::new()
+        }
+    )];
+
+    for part in report.parts.iter() {
+        rows.push(format!(
+            "{},{},{},{},{},{},{},{},{}",
+            report.year,
+            report.day,
+            part.part,
+            csv_name(part.status),
+            part.answer
+                .as_ref()
+                .filter(|answer| answer.is_implemented())
+                .map(|answer| csv_field(&answer.to_string()))
+                .unwrap_or_default(),
+            part.check.map(csv_name).unwrap_or_default(),
+            time(part.time).unwrap_or_default(),
+            alloc(part.alloc),
+            if matches!(part.status, Status::Failed | Status::TimedOut) {
+                csv_field(&error)
+            } else {
+                String::new()
+            }
+        ));
+    }
+    rows
+}
+
+/// writes reports to stdout as they come in, json output is a single array of day records
+/// and csv has one row per phase (parse, a, b) of every day
+pub struct Printer {
+    format: Format,
+    printed: usize,
+}
+
+impl Printer {
+    pub fn new(format: Format) -> Printer {
+        match format {
+            Format::Text => {}
+            Format::Json => println!("["),
+            Format::Csv => println!("{CSV_HEADER}"),
+        }
+
+        Printer { format, printed: 0 }
+    }
+
+    pub fn print(&mut self, report: &DayReport) {
+        match self.format {
+            Format::Text => print_text(report),
+            Format::Json => {
+                if self.printed > 0 {
+                    println!(",");
+                }
+                print!("{}", serde_json::to_string_pretty(report).unwrap());
+            }
+            Format::Csv => {
+                for row in csv_rows(report) {
+                    println!("{row}");
+                }
+            }
+        }
+
+        self.printed += 1;
+    }
+
+    /// json and csv output stays parseable, so summaries (and comparisons) go to stderr for them
+    pub fn summary(&self, summary: &impl fmt::Display) {
+        match self.format {
+            Format::Text => println!("{summary}"),
+            Format::Json | Format::Csv => eprintln!("{summary}"),
+        }
+    }
+
+    pub fn finish(self) {
+        if self.format == Format::Json {
+            if self.printed > 0 {
+                println!();
+            }
+            println!("]");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::Part;
+
+    fn parse_failed() -> DayReport {
+        let mut report = DayReport::new(2024, 3);
+        report.error = Some(AocError::parse(2, 5, "x", "expected a number"));
+        report
+    }
+
+    fn timed_out() -> DayReport {
+        let mut report = DayReport::new(2024, 23);
+        report.parse_time = Some(Duration::from_nanos(1500));
+        report.parts[0].status = Status::Ok;
+        report.parts[0].answer = Some(Answer::Str("co,de,\"ka\",ta".to_string()));
+        report.parts[0].check = Some(Check::Unknown);
+        report.parts[0].time = Some(Duration::from_nanos(2000));
+        report.parts[1].status = Status::TimedOut;
+        report.error = Some(AocError::TimedOut {
+            part: Part::B,
+            limit: Duration::from_secs(1),
+        });
+        report
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            csv_rows(&parse_failed()),
+            vec![
+                "2024,3,parse,failed,,,,,,,\"failed to parse input at line 2, column 5: \
+                 expected a number (found \"\"x\"\")\"",
+                "2024,3,a,skipped,,,,,,,",
+                "2024,3,b,skipped,,,,,,,",
+            ]
+        );
+        assert_eq!(
+            csv_rows(&timed_out()),
+            vec![
+                "2024,23,parse,ok,,,1500,,,,",
+                "2024,23,a,ok,\"co,de,\"\"ka\"\",ta\",unknown,2000,,,,",
+                "2024,23,b,timed_out,,,,,,,part b timed out after 1.00s",
+            ]
+        );
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            serde_json::to_value(parse_failed()).unwrap(),
+            serde_json::json!({
+                "year": 2024,
+                "day": 3,
+                "parse_time_ns": null,
+                "parts": [
+                    {"part": "a", "status": "skipped", "answer": null, "time_ns": null},
+                    {"part": "b", "status": "skipped", "answer": null, "time_ns": null},
+                ],
+                "error": "failed to parse input at line 2, column 5: expected a number (found \"x\")",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(timed_out()).unwrap(),
+            serde_json::json!({
+                "year": 2024,
+                "day": 23,
+                "parse_time_ns": 1500,
+                "parts": [
+                    {
+                        "part": "a",
+                        "status": "ok",
+                        "answer": "co,de,\"ka\",ta",
+                        "check": "unknown",
+                        "time_ns": 2000,
+                    },
+                    {"part": "b", "status": "timed_out", "answer": null, "time_ns": null},
+                ],
+                "error": "part b timed out after 1.00s",
+            })
+        );
+    }
+}
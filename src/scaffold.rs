@@ -0,0 +1,180 @@
+use crate::day;
+use crate::error::AocError;
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+// the module a `mod name;` line declares
+fn mod_name(line: &str) -> Option<&str> {
+    line.strip_prefix("mod ")?
+        .strip_suffix(';')
+        .filter(|name| name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+}
+
+// adds `mod name;` to the last block of mod lines in the order rustfmt sorts them in, as strings
+fn add_mod(text: &str, name: &str) -> String {
+    let line = format!("mod {name};");
+    let mut lines = text.lines().collect::<Vec<_>>();
+
+    let at = match lines.iter().rposition(|l| mod_name(l).is_some()) {
+        Some(end) => {
+            let start = lines[..end]
+                .iter()
+                .rposition(|l| mod_name(l).is_none())
+                .map_or(0, |before| before + 1);
+            (start..=end)
+                .find(|i| mod_name(lines[*i]).is_some_and(|m| m > name))
+                .unwrap_or(end + 1)
+        }
+        None => lines.len(),
+    };
+
+    lines.insert(at, &line);
+    lines.join("\n") + "\n"
+}
+
+fn declares(text: &str, name: &str) -> bool {
+    text.lines().any(|l| mod_name(l.trim()) == Some(name))
+}
+
+/// writes src/y{year}/day{N}.rs from templates/day.rs, an empty goldens/{year}/day{N} and
+/// declares the module in src/y{year}.rs (and that one in src/main.rs), all under root.
+/// nothing is written if any of them is already there
+pub fn new_day(root: &Path, year: u32, day: u32) -> Result<Vec<PathBuf>, AocError> {
+    let year_mod = format!("y{year}");
+    let day_mod = format!("day{day}");
+    let source = root.join(format!("src/{year_mod}/{day_mod}.rs"));
+    let golden = root.join(day::goldens_dir(year)).join(&day_mod);
+    let year_rs = root.join(format!("src/{year_mod}.rs"));
+    let main_rs = root.join("src/main.rs");
+
+    let main = fs::read_to_string(&main_rs).map_err(|err| AocError::io(&main_rs, err))?;
+    let days = match fs::read_to_string(&year_rs) {
+        Ok(days) => days,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            format!("// the days of {year}, new --day adds them here\n")
+        }
+        Err(err) => return Err(AocError::io(&year_rs, err)),
+    };
+
+    if declares(&days, &day_mod) {
+        return Err(AocError::Config(format!(
+            "day {day} of {year} already exists, {} declares it",
+            year_rs.display()
+        )));
+    }
+    for path in [&source, &golden] {
+        if path.exists() {
+            return Err(AocError::Config(format!(
+                "day {day} of {year} already exists, there is a {}",
+                path.display()
+            )));
+        }
+    }
+
+    let code = TEMPLATE
+        .replace("{day}", &day.to_string())
+        .replace("{year}", &year.to_string());
+
+    // create_new so a file that showed up since the check above is not overwritten
+    let create = |path: &Path, contents: &str| {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|err| AocError::io(dir, err))?;
+        }
+        fs::File::create_new(path)
+            .and_then(|mut file| file.write_all(contents.as_bytes()))
+            .map_err(|err| AocError::io(path, err))
+    };
+
+    create(&source, &code)?;
+    create(&golden, "")?;
+    fs::write(&year_rs, add_mod(&days, &day_mod)).map_err(|err| AocError::io(&year_rs, err))?;
+
+    let mut written = vec![source, golden, year_rs];
+    if !declares(&main, &year_mod) {
+        fs::write(&main_rs, add_mod(&main, &year_mod))
+            .map_err(|err| AocError::io(&main_rs, err))?;
+        written.push(main_rs);
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, process};
+
+    #[test]
+    fn adds_mod_in_order() {
+        let main = "mod all;\nmod day;\n\nmod day1;\nmod day10;\nmod day2;\nmod utils;\n";
+
+        assert_eq!(
+            add_mod(main, "day11"),
+            "mod all;\nmod day;\n\nmod day1;\nmod day10;\nmod day11;\nmod day2;\nmod utils;\n"
+        );
+        assert_eq!(
+            add_mod(main, "day3"),
+            "mod all;\nmod day;\n\nmod day1;\nmod day10;\nmod day2;\nmod day3;\nmod utils;\n"
+        );
+        assert_eq!(
+            add_mod(main, "y2023"),
+            "mod all;\nmod day;\n\nmod day1;\nmod day10;\nmod day2;\nmod utils;\nmod y2023;\n"
+        );
+        assert_eq!(add_mod("// days\n", "day1"), "// days\nmod day1;\n");
+    }
+
+    #[test]
+    fn scaffolds_once() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}", process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/main.rs"), "mod day1;\nmod day2;\n").unwrap();
+
+        let created = new_day(&root, 2024, 12).unwrap();
+        assert_eq!(created.len(), 4);
+
+        let code = fs::read_to_string(root.join("src/y2024/day12.rs")).unwrap();
+        assert!(code.contains("pub struct Day12;"));
+        assert!(code.contains("registry::register!(2024, 12, Day12);"));
+        assert!(code.contains("utils::golden(\"2024/day12\""));
+        assert_eq!(
+            fs::read_to_string(root.join("goldens/2024/day12")).unwrap(),
+            ""
+        );
+        assert!(fs::read_to_string(root.join("src/y2024.rs"))
+            .unwrap()
+            .ends_with("\nmod day12;\n"));
+        assert_eq!(
+            fs::read_to_string(root.join("src/main.rs")).unwrap(),
+            "mod day1;\nmod day2;\nmod y2024;\n"
+        );
+
+        // a second time changes nothing
+        fs::write(root.join("src/y2024/day12.rs"), "edited").unwrap();
+        assert!(matches!(new_day(&root, 2024, 12), Err(AocError::Config(_))));
+        assert_eq!(
+            fs::read_to_string(root.join("src/y2024/day12.rs")).unwrap(),
+            "edited"
+        );
+
+        // the same day of another year is a different day
+        let created = new_day(&root, 2023, 12).unwrap();
+        assert_eq!(created.len(), 4);
+        assert_eq!(
+            fs::read_to_string(root.join("src/main.rs")).unwrap(),
+            "mod day1;\nmod day2;\nmod y2023;\nmod y2024;\n"
+        );
+
+        // another day of a year that is there only adds to its module
+        assert_eq!(new_day(&root, 2024, 3).unwrap().len(), 3);
+        assert!(fs::read_to_string(root.join("src/y2024.rs"))
+            .unwrap()
+            .ends_with("\nmod day12;\nmod day3;\n"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
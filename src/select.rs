@@ -0,0 +1,153 @@
+use crate::day::{DayRunner, Part};
+use crate::error::AocError;
+use crate::registry::Registry;
+use clap::ValueEnum;
+use std::ops::RangeInclusive;
+
+/// which parts of a day to run
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Parts {
+    A,
+    B,
+    #[default]
+    Both,
+}
+
+impl Parts {
+    pub fn includes(self, part: Part) -> bool {
+        matches!(
+            (self, part),
+            (Parts::Both, _) | (Parts::A, Part::A) | (Parts::B, Part::B)
+        )
+    }
+}
+
+/// a list of days and day ranges like 1-5,9,12-
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DaySelector {
+    items: Vec<RangeInclusive<u32>>,
+}
+
+impl DaySelector {
+    pub fn all() -> DaySelector {
+        DaySelector {
+            items: vec![1..=25],
+        }
+    }
+
+    pub fn contains(&self, day: u32) -> bool {
+        self.items.iter().any(|range| range.contains(&day))
+    }
+
+    /// the day when exactly one was selected
+    pub fn single(&self) -> Option<u32> {
+        match self.items.as_slice() {
+            [range] if range.start() == range.end() => Some(*range.start()),
+            _ => None,
+        }
+    }
+
+    /// the selected days that have a solution for year, in order.
+    /// days named on their own have to exist, ranges just skip the missing ones
+    pub fn resolve(
+        &self,
+        registry: &Registry,
+        year: u32,
+    ) -> Result<Vec<(u32, DayRunner)>, AocError> {
+        for range in self.items.iter() {
+            if range.start() == range.end() {
+                registry.get(year, *range.start())?;
+            }
+        }
+
+        Ok(registry
+            .days(year)
+            .filter(|(day, _)| self.contains(*day))
+            .collect())
+    }
+}
+
+fn day(arg: &str, part: &str) -> Result<u32, String> {
+    let day = part
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| format!("invalid day {part:?} in {arg:?}"))?;
+
+    if !(1..=25).contains(&day) {
+        return Err(format!(
+            "day {day} in {arg:?} is out of range, days go from 1 to 25"
+        ));
+    }
+    Ok(day)
+}
+
+/// clap value parser for day selectors: comma separated days (9), ranges (1-5) and open ranges (12-, -3)
+pub fn parse_days(arg: &str) -> Result<DaySelector, String> {
+    let items = arg
+        .split(',')
+        .map(|item| {
+            let range = match item.split_once('-') {
+                Some((start, end)) => {
+                    let start = if start.trim().is_empty() {
+                        1
+                    } else {
+                        day(arg, start)?
+                    };
+                    let end = if end.trim().is_empty() {
+                        25
+                    } else {
+                        day(arg, end)?
+                    };
+                    start..=end
+                }
+                None => {
+                    let day = day(arg, item)?;
+                    day..=day
+                }
+            };
+
+            if range.is_empty() {
+                return Err(format!(
+                    "{item:?} is an empty range, it starts after it ends"
+                ));
+            }
+            Ok(range)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(DaySelector { items })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selectors() {
+        let days = parse_days("1-5,9,12-").unwrap();
+        let selected = (1..=25)
+            .filter(|day| days.contains(*day))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            selected,
+            vec![1, 2, 3, 4, 5, 9, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25]
+        );
+
+        assert_eq!(parse_days("7").unwrap().single(), Some(7));
+        assert_eq!(parse_days("-3").unwrap().single(), None);
+        assert!(parse_days("-3").unwrap().contains(1));
+
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("5-3").is_err());
+        assert!(parse_days("1,,2").is_err());
+        assert!(parse_days("a-b").is_err());
+    }
+
+    #[test]
+    fn parts() {
+        assert!(Parts::Both.includes(Part::A));
+        assert!(Parts::B.includes(Part::B));
+        assert!(!Parts::B.includes(Part::A));
+    }
+}
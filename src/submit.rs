@@ -0,0 +1,208 @@
+use crate::day::{FetchConfig, Part};
+use crate::error::AocError;
+use crate::unlock;
+use chrono::Utc;
+use colored::Colorize;
+use core::fmt;
+use regex::Regex;
+use reqwest::header::USER_AGENT;
+
+/// what adventofcode.com said about a submitted answer
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// submitted too soon after the last answer, holds the remaining time as given by the site ("34s", "1m 5s")
+    RateLimited(String),
+    /// the part was already solved (or part b is not unlocked yet)
+    WrongLevel,
+    /// anything else, with the text of the response
+    Unknown(String),
+}
+
+impl Verdict {
+    pub fn is_correct(&self) -> bool {
+        *self == Verdict::Correct
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "{}", "that's the right answer".green()),
+            Verdict::TooHigh => write!(f, "{}", "wrong answer, too high".red()),
+            Verdict::TooLow => write!(f, "{}", "wrong answer, too low".red()),
+            Verdict::Wrong => write!(f, "{}", "wrong answer".red()),
+            Verdict::RateLimited(wait) => write!(
+                f,
+                "{}",
+                format!("answered too recently, wait {wait} before trying again").yellow()
+            ),
+            Verdict::WrongLevel => write!(
+                f,
+                "{}",
+                "not solving the right level, is this part already done?".yellow()
+            ),
+            Verdict::Unknown(text) => write!(f, "unrecognized response: {text}"),
+        }
+    }
+}
+
+/// the level form field the site expects for a part
+fn level(part: Part) -> &'static str {
+    match part {
+        Part::A => "1",
+        Part::B => "2",
+    }
+}
+
+pub fn parse_response(html: &str) -> Verdict {
+    // only the <article> holds the message, the rest is the page around it
+    let article = Regex::new(r"(?s)<article>(.*)</article>")
+        .unwrap()
+        .captures(html)
+        .map_or(html, |c| c.get(1).unwrap().as_str());
+    let text = Regex::new(r"<[^>]*>").unwrap().replace_all(article, "");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("your answer is too high") {
+        Verdict::TooHigh
+    } else if text.contains("your answer is too low") {
+        Verdict::TooLow
+    } else if text.contains("That's not the right answer") {
+        Verdict::Wrong
+    } else if let Some(wait) = Regex::new(r"You have (.+?) left to wait")
+        .unwrap()
+        .captures(&text)
+    {
+        Verdict::RateLimited(wait[1].to_string())
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown(text)
+    }
+}
+
+/// posts answer for the part to base_url/{year}/day/{day}/answer
+pub fn submit(
+    base_url: &str,
+    conf: &FetchConfig,
+    year: u32,
+    day: u32,
+    part: Part,
+    answer: &str,
+) -> Result<Verdict, AocError> {
+    unlock::check_released(year, day, Utc::now())?;
+    let url = format!("{base_url}/{year}/day/{day}/answer");
+
+    let res = reqwest::blocking::Client::new()
+        .post(&url)
+        .header("Cookie", format!("session={};", conf.oauthkey))
+        .header(USER_AGENT, &conf.agent)
+        .form(&[("level", level(part)), ("answer", answer)])
+        .send()
+        .map_err(|source| AocError::Fetch {
+            url: url.clone(),
+            source,
+        })?;
+
+    if !res.status().is_success() {
+        return Err(AocError::Http {
+            url,
+            status: res.status(),
+        });
+    }
+
+    let html = res
+        .text()
+        .map_err(|source| AocError::Fetch { url, source })?;
+
+    Ok(parse_response(&html))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::stand_in_server;
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main>\n<article><p>{message}</p></article>\n</main></body></html>")
+    }
+
+    fn conf() -> FetchConfig {
+        FetchConfig {
+            year: Some(2023),
+            agent: "tests".to_string(),
+            oauthkey: "cafe".to_string(),
+        }
+    }
+
+    #[test]
+    fn responses() {
+        assert_eq!(
+            parse_response(&page(
+                "That's the right answer!  You are <em>one gold star</em> closer to finding the Chief Historian."
+            )),
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data."
+            )),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer.  If you're stuck, ...")),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait. <a href=\"/2024/day/3\">[Return to Day 3]</a>"
+            )),
+            Verdict::RateLimited("34s".to_string())
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Verdict::WrongLevel
+        );
+        assert_eq!(
+            parse_response(&page("Something <b>else</b>")),
+            Verdict::Unknown("Something else".to_string())
+        );
+    }
+
+    #[test]
+    fn posts_answer() {
+        let (url, server) = stand_in_server(200, &page("That's the right answer!"));
+
+        let verdict = submit(&url, &conf(), 2024, 3, Part::B, "1234").unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(verdict, Verdict::Correct);
+        assert!(request.starts_with("POST /2024/day/3/answer "));
+        assert!(request.contains("session=cafe;"));
+        assert!(request.ends_with("level=2&answer=1234"));
+    }
+
+    #[test]
+    fn http_error() {
+        let (url, server) = stand_in_server(500, "");
+
+        let err = submit(&url, &conf(), 2024, 3, Part::A, "1").unwrap_err();
+        server.join().unwrap();
+
+        assert!(matches!(err, AocError::Http { status, .. } if status == 500));
+    }
+}
@@ -0,0 +1,125 @@
+use crate::error::AocError;
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use std::{
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+
+/// puzzles unlock at midnight EST (UTC-5) on december {day} of their year,
+/// this is a couple of seconds later so the site has it by the time we ask
+pub fn unlock_time(year: u32, day: u32) -> Option<DateTime<Utc>> {
+    FixedOffset::west_opt(5 * 60 * 60)
+        .unwrap()
+        .with_ymd_and_hms(year as i32, 12, day, 0, 0, 2)
+        .single()
+        .map(|time| time.to_utc())
+}
+
+pub fn released(year: u32, day: u32, current_time: DateTime<Utc>) -> bool {
+    unlock_time(year, day).is_some_and(|unlock| current_time > unlock)
+}
+
+/// how long until the puzzle unlocks, zero once it has
+fn time_left(year: u32, day: u32, current_time: DateTime<Utc>) -> Duration {
+    unlock_time(year, day)
+        .and_then(|unlock| (unlock - current_time).to_std().ok())
+        .unwrap_or(Duration::ZERO)
+}
+
+/// Locked when the puzzle can not be fetched yet, call before anything that asks the site for it
+pub fn check_released(year: u32, day: u32, current_time: DateTime<Utc>) -> Result<(), AocError> {
+    if released(year, day, current_time) {
+        Ok(())
+    } else {
+        Err(AocError::Locked {
+            year,
+            day,
+            unlocks_in: time_left(year, day, current_time),
+        })
+    }
+}
+
+/// like 2d 03:04:05, the days only when there are any
+pub fn countdown(left: Duration) -> String {
+    let secs = left.as_secs();
+    let clock = format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600 % 24,
+        secs / 60 % 60,
+        secs % 60
+    );
+    match secs / (24 * 3600) {
+        0 => clock,
+        days => format!("{days}d {clock}"),
+    }
+}
+
+/// blocks until the puzzle unlocks, counting down on stderr
+pub fn wait_until_released(year: u32, day: u32) {
+    let mut shown = false;
+
+    loop {
+        let now = Utc::now();
+        if released(year, day, now) {
+            break;
+        }
+        let left = time_left(year, day, now);
+
+        eprint!(
+            "\rday {day} of {year} unlocks in {} ",
+            countdown(left + Duration::from_millis(999))
+        );
+        // the countdown is only for show, nothing to do if stderr is gone
+        let _ = io::stderr().flush();
+        shown = true;
+
+        // sleep to the next whole second so the countdown ticks evenly
+        let tick = Duration::from_nanos(left.subsec_nanos().into());
+        thread::sleep(if tick.is_zero() {
+            Duration::from_secs(1)
+        } else {
+            tick
+        });
+    }
+
+    if shown {
+        eprintln!("\rday {day} of {year} is unlocked{}", " ".repeat(12));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().to_utc()
+    }
+
+    #[test]
+    fn release_gating() {
+        assert!(!released(2024, 3, time("2024-12-03T04:59:00Z")));
+        assert!(released(2024, 3, time("2024-12-03T05:00:03Z")));
+        assert!(released(2023, 25, time("2024-01-01T00:00:00Z")));
+        assert!(!released(2025, 1, time("2024-12-25T00:00:00Z")));
+    }
+
+    #[test]
+    fn locked() {
+        assert!(check_released(2024, 3, time("2024-12-03T05:00:03Z")).is_ok());
+        assert!(matches!(
+            check_released(2024, 3, time("2024-12-02T04:00:02Z")),
+            Err(AocError::Locked { unlocks_in, .. }) if unlocks_in == Duration::from_secs(25 * 3600)
+        ));
+    }
+
+    #[test]
+    fn countdowns() {
+        assert_eq!(countdown(Duration::from_secs(59)), "00:00:59");
+        assert_eq!(countdown(Duration::from_secs(3 * 3600 + 61)), "03:01:01");
+        assert_eq!(
+            countdown(Duration::from_secs(2 * 24 * 3600 + 3600)),
+            "2d 01:00:00"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use crate::error::AocError;
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+thread_local! {
+    // set while this thread is inside catch, the hook then only records where the panic happened
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static HOOK: Once = Once::new();
+
+fn install_hook() {
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() {
+                LOCATION.set(info.location().map(|l| l.to_string()));
+            } else {
+                default(info);
+            }
+        }));
+    });
+}
+
+fn message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "panic without a message".to_string()
+    }
+}
+
+/// runs f, turning a panic in it into AocError::Panic instead of unwinding further.
+/// panics caught here are not printed by the default hook, they end up in the day's report.
+/// (panics on other threads f starts, like rayon's, still print but are caught once they reach f)
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, AocError> {
+    install_hook();
+
+    let was_catching = CATCHING.replace(true);
+    let res = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(was_catching);
+
+    res.map_err(|payload| AocError::Panic {
+        message: message(payload.as_ref()),
+        location: LOCATION.take(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catches_message_and_location() {
+        let line = line!() + 1;
+        let err = catch(|| panic!("no path found")).unwrap_err();
+
+        match err {
+            AocError::Panic { message, location } => {
+                assert_eq!(message, "no path found");
+                assert!(location
+                    .unwrap()
+                    .starts_with(&format!("src/unwind.rs:{line}:")));
+            }
+            err => panic!("expected a panic error, got {err}"),
+        }
+
+        assert_eq!(catch(|| 5).unwrap(), 5);
+    }
+}
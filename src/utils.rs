@@ -1,56 +1,205 @@
+use std::{fmt, iter, str::FromStr};
+
+use crate::error::AocError;
+
+#[cfg(test)]
+use std::{fs, path::Path};
+
+#[cfg(test)]
+use crate::answer::Answer;
+
+#[cfg(test)]
+use crate::day::{self, Part, Solution};
+
 #[cfg(test)]
-use std::fs;
+use crate::known::{answers_path, Check, KnownAnswers};
 
 #[cfg(test)]
-use once_cell::sync::Lazy;
+use crate::log::Log;
+
+#[cfg(test)]
+use crate::params::Params;
+
+/// a line of puzzle input and its (0 indexed) row, so parsers can point
+/// at the exact spot that could not be parsed
+#[derive(Clone, Copy)]
+pub struct Line<'a> {
+    pub row: usize,
+    pub text: &'a str,
+}
+
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(row, text)| Line { row, text })
+}
+
+impl<'a> Line<'a> {
+    // field is expected to be a slice of the line, anything else is reported at column 1
+    fn column(&self, field: &str) -> usize {
+        let offset = (field.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+
+        if offset <= self.text.len() && self.text.is_char_boundary(offset) {
+            self.text[..offset].chars().count() + 1
+        } else {
+            1
+        }
+    }
+
+    pub fn error(&self, field: &str, message: impl Into<String>) -> AocError {
+        AocError::parse(self.row + 1, self.column(field), field, message)
+    }
+
+    pub fn num<T>(&self, field: &str) -> Result<T, AocError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        field
+            .parse::<T>()
+            .map_err(|err| self.error(field, err.to_string()))
+    }
+
+    pub fn split(&self, field: &'a str, sep: &str) -> Result<(&'a str, &'a str), AocError> {
+        field
+            .split_once(sep)
+            .ok_or_else(|| self.error(field, format!("expected {:?}", sep)))
+    }
+
+    pub fn strip(&self, field: &'a str, prefix: &str) -> Result<&'a str, AocError> {
+        field
+            .strip_prefix(prefix)
+            .ok_or_else(|| self.error(field, format!("expected {:?}", prefix)))
+    }
+}
+
+/// parses every character of every line with cell, None marks an invalid character
+pub fn grid<'a, T>(
+    lines: impl Iterator<Item = Line<'a>>,
+    cell: impl Fn(char) -> Option<T>,
+) -> Result<Vec<Vec<T>>, AocError> {
+    lines
+        .map(|line| {
+            line.text
+                .char_indices()
+                .map(|(i, c)| {
+                    cell(c).ok_or_else(|| {
+                        line.error(&line.text[i..i + c.len_utf8()], "invalid character")
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// parses the arguments exe passed on to a visualization, None when they asked for --help
+/// and it was printed
+pub fn visualize_args<T: clap::Parser>(day: u32, args: &[String]) -> Result<Option<T>, AocError> {
+    let name = format!("aoc exe --day {day} --");
+    match T::try_parse_from(iter::once(name.as_str()).chain(args.iter().map(|a| a.as_str()))) {
+        Ok(parsed) => Ok(Some(parsed)),
+        Err(err) if err.kind() == clap::error::ErrorKind::DisplayHelp => {
+            print!("{err}");
+            Ok(None)
+        }
+        Err(err) => {
+            // clap starts its messages with error: too, which print_error already adds
+            let msg = err.render().to_string();
+            let msg = msg.strip_prefix("error: ").unwrap_or(&msg).trim_end();
+            Err(AocError::Config(msg.to_string()))
+        }
+    }
+}
 
 #[cfg(test)]
-use crate::day::Day;
+pub fn golden<S: Solution>(
+    file: &str,
+    parent: &S,
+    expected_a: Option<Answer>,
+    expected_b: Option<Answer>,
+    verbose: bool,
+) {
+    golden_params(file, parent, &[], expected_a, expected_b, verbose)
+}
 
+// same as golden but overrides some of the day's parameters,
+// examples often use a smaller grid or fewer steps than the real puzzle
 #[cfg(test)]
-pub fn golden<'a, Input>(
-    file: &'a str,
-    parent: &'a Lazy<Day<Input>>,
-    expected_a: Option<&'a str>,
-    expected_b: Option<&'a str>,
+pub fn golden_params<S: Solution>(
+    file: &str,
+    parent: &S,
+    params: &[(&str, i64)],
+    expected_a: Option<Answer>,
+    expected_b: Option<Answer>,
     verbose: bool,
 ) {
-    let input = match fs::read_to_string(format!("goldens/{}", file)) {
+    let input = match fs::read_to_string(Path::new(day::GOLDENS_DIR).join(file)) {
         Ok(a) => a,
         Err(_) => panic!("golden {} failed: could not open file", file),
     };
 
-    let parent = Lazy::force(parent);
+    let params = params
+        .iter()
+        .map(|(name, value)| (name.to_string(), *value))
+        .collect::<Vec<_>>();
+    let params = Params::new(&parent.params(), &params)
+        .unwrap_or_else(|err| panic!("golden {} failed: {}", file, err));
+
+    // goldens are named {year}/day{N} or {year}/day{N}-something
+    let day = file
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches("day")
+        .split(|c: char| !c.is_ascii_digit())
+        .next()
+        .and_then(|day| day.parse().ok())
+        .unwrap_or(0);
+    let log = Log::new(day, verbose as u8);
 
-    let input = match (*parent.parser)(input, verbose) {
+    let input = match parent.parse(input, &log) {
         Ok(a) => a,
         Err(err) => panic!("golden {} failed to parse: {}", file, err),
     };
 
-    if let Some(_a) = expected_a {
-        let part_a = (*parent.part_a)(&input);
-        if expected_a != part_a.as_deref() {
+    if let Some(expected_a) = expected_a {
+        let part_a = parent
+            .part_a(&input, &params, &log)
+            .unwrap_or_else(|err| panic!("golden {} failed: {}", file, err));
+        if expected_a != part_a {
             panic!("golden {} expected {:?} got {:?}", file, expected_a, part_a);
         }
     }
 
-    if let Some(_a) = expected_b {
-        let part_b = (*parent.part_b)(&input);
-        if expected_b != part_b.as_deref() {
+    if let Some(expected_b) = expected_b {
+        let part_b = parent
+            .part_b(&input, &params, &log)
+            .unwrap_or_else(|err| panic!("golden {} failed: {}", file, err));
+        if expected_b != part_b {
             panic!("golden {} expected {:?} got {:?}", file, expected_b, part_b);
         }
     }
 }
 
+// checks the day against the answers recorded for it in answers/{year}.toml
 #[cfg(test)]
-pub fn finalanswer<'a, Input>(
-    daynum: usize,
-    parent: &'a Lazy<Day<Input>>,
-    expected_a: Option<&'a str>,
-    expected_b: Option<&'a str>,
-    verbose: bool,
-) {
-    let input = match fs::read_to_string(format!("inputs/day{}", daynum)) {
+pub fn finalanswer<S: Solution>(year: u32, daynum: usize, parent: &S, verbose: bool) {
+    let known = KnownAnswers::load(year)
+        .unwrap_or_else(|err| panic!("regression test for day: {} failed: {}", daynum, err));
+    let day = daynum as u32;
+    if [Part::A, Part::B]
+        .iter()
+        .all(|part| known.get(day, *part).is_none())
+    {
+        panic!(
+            "regression test for day: {} failed: no answers for it in {}",
+            daynum,
+            answers_path(year).display()
+        );
+    }
+
+    let input = match fs::read_to_string(day::input_path(year, day)) {
         Ok(a) => a,
         Err(_) => panic!(
             "regression test for day: {} failed: could not open file",
@@ -58,9 +207,12 @@ pub fn finalanswer<'a, Input>(
         ),
     };
 
-    let parent = Lazy::force(parent);
+    let params = Params::new(&parent.params(), &[])
+        .unwrap_or_else(|err| panic!("regression test for day: {} failed: {}", daynum, err));
 
-    let input = match (*parent.parser)(input, verbose) {
+    let log = Log::new(day, verbose as u8);
+
+    let input = match parent.parse(input, &log) {
         Ok(a) => a,
         Err(err) => panic!(
             "regression test for day: {} failed to parse: {}",
@@ -68,49 +220,70 @@ pub fn finalanswer<'a, Input>(
         ),
     };
 
-    let part_a = (*parent.part_a)(&input);
-    let part_b = (*parent.part_b)(&input);
+    for part in [Part::A, Part::B] {
+        let Some(expected) = known.get(day, part) else {
+            continue;
+        };
 
-    if let Some(_a) = expected_a {
-        if expected_a != part_a.as_deref() {
-            panic!(
-                "regression test for day: {} expected {:?} got {:?}",
-                daynum, expected_a, part_a
-            );
+        let answer = match part {
+            Part::A => parent.part_a(&input, &params, &log),
+            Part::B => parent.part_b(&input, &params, &log),
         }
-    }
+        .unwrap_or_else(|err| panic!("regression test for day: {} failed: {}", daynum, err));
 
-    if let Some(_a) = expected_b {
-        if expected_b != part_b.as_deref() {
+        if known.check(day, part, &answer) != Check::Correct {
             panic!(
-                "regression test for day: {} expected {:?} got {:?}",
-                daynum, expected_b, part_b
+                "regression test for day: {} part {} expected {} got {}",
+                daynum, part, expected, answer
             );
         }
     }
 }
 
+// answers a single http request on a local port with the given status and body,
+// the handle returns the raw request (headers and body) so tests can check what was sent
 #[cfg(test)]
-pub fn set_function<'a, Input>(
-    file: &'a str,
-    parser: &dyn Fn(String, bool) -> Result<Input, String>,
-    function: &dyn Fn(&Input) -> String,
-    expected: &'a str,
-    verbose: bool,
-) {
-    let input = match fs::read_to_string(format!("goldens/{}", file)) {
-        Ok(a) => a,
-        Err(_) => panic!("golden {} failed: could not open file", file),
-    };
+pub fn stand_in_server(status: u16, body: &str) -> (String, std::thread::JoinHandle<String>) {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
 
-    let input = match (*parser)(input, verbose) {
-        Ok(a) => a,
-        Err(err) => panic!("golden {} failed to parse: {}", file, err),
-    };
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let body = body.to_string();
 
-    let res = (*function)(&input);
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
 
-    if expected != &res {
-        panic!("golden {} expected {:?} got {:?}", file, expected, res);
-    }
+        let mut request = String::new();
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+            request += &line;
+            if line == "\r\n" || line.is_empty() {
+                break;
+            }
+        }
+
+        let mut content = vec![0; length];
+        reader.read_exact(&mut content).unwrap();
+        request += &String::from_utf8_lossy(&content);
+
+        write!(
+            reader.get_mut(),
+            "HTTP/1.1 {status} OK\r\ncontent-type: text/html\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+
+        request
+    });
+
+    (url, handle)
 }
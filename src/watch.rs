@@ -0,0 +1,305 @@
+use crate::answer::Answer;
+use crate::day::{self, DayRunner, RunOptions};
+use crate::error::AocError;
+use crate::report::{print_error, DayReport, Format, Printer};
+use crate::utils;
+use colored::Colorize;
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// what a golden should give, read from the <golden>.expected file next to it.
+/// it has lines of `a = 11` and `b = 31`, any other name = value overrides a parameter
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Expected {
+    pub a: Option<String>,
+    pub b: Option<String>,
+    pub params: Vec<(String, i64)>,
+}
+
+pub fn parse_expected(text: &str) -> Result<Expected, AocError> {
+    let mut expected = Expected::default();
+
+    for line in utils::lines(text) {
+        if line.text.trim().is_empty() || line.text.starts_with('#') {
+            continue;
+        }
+
+        let (name, value) = line.split(line.text, "=")?;
+        let (name, value) = (name.trim(), value.trim());
+        match name {
+            "a" => expected.a = Some(value.to_string()),
+            "b" => expected.b = Some(value.to_string()),
+            _ => expected
+                .params
+                .push((name.to_string(), line.num::<i64>(value)?)),
+        }
+    }
+
+    Ok(expected)
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (part, answer) in [("a", &self.a), ("b", &self.b)] {
+            if let Some(answer) = answer {
+                writeln!(f, "{part} = {answer}")?;
+            }
+        }
+        for (name, value) in self.params.iter() {
+            writeln!(f, "{name} = {value}")?;
+        }
+        Ok(())
+    }
+}
+
+pub fn expected_path(golden: &Path) -> PathBuf {
+    let mut path = golden.as_os_str().to_owned();
+    path.push(".expected");
+    PathBuf::from(path)
+}
+
+// day1 and day1-2 belong to day 1, day12 does not
+fn is_golden_of(name: &str, day: u32) -> bool {
+    match name.strip_prefix(&format!("day{day}")) {
+        Some(rest) => !rest.starts_with(|c: char| c.is_ascii_digit()),
+        None => false,
+    }
+}
+
+/// the golden inputs of a day in goldens/{year}, sorted by name
+pub fn goldens(year: u32, day: u32) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(day::goldens_dir(year)) else {
+        return Vec::new();
+    };
+
+    let mut goldens = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            is_golden_of(&name, day) && !name.ends_with(".expected")
+        })
+        .collect::<Vec<_>>();
+    goldens.sort();
+    goldens
+}
+
+// everything the watch looks at, with when it was last changed
+fn stamps(input: &Path, year: u32, day: u32) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut files = vec![input.to_path_buf()];
+    for golden in goldens(year, day) {
+        files.push(expected_path(&golden));
+        files.push(golden);
+    }
+
+    files
+        .into_iter()
+        .map(|file| {
+            let modified = fs::metadata(&file).and_then(|m| m.modified()).ok();
+            (file, modified)
+        })
+        .collect()
+}
+
+type Answers = [Option<Answer>; 2];
+
+fn answers(report: &DayReport) -> Answers {
+    report.parts.each_ref().map(|part| part.answer.clone())
+}
+
+// prints the parts whose answer is not the same as in the last run
+fn print_changes(name: &str, now: &Answers, previous: Option<&Answers>) {
+    let Some(previous) = previous else {
+        return;
+    };
+
+    for (part, (before, after)) in ['a', 'b'].into_iter().zip(previous.iter().zip(now)) {
+        if before != after {
+            let show = |answer: &Option<Answer>| match answer {
+                Some(answer) => answer.to_string(),
+                None => "nothing".to_string(),
+            };
+            println!(
+                "{}",
+                format!(
+                    "{name} part {part} changed: {} -> {}",
+                    show(before),
+                    show(after)
+                )
+                .yellow()
+            );
+        }
+    }
+}
+
+fn check_golden(day: u32, runner: DayRunner, options: &RunOptions, golden: &Path) -> Answers {
+    let name = golden.file_name().unwrap_or_default().to_string_lossy();
+
+    let expected = match fs::read_to_string(expected_path(golden)) {
+        Ok(text) => match parse_expected(&text) {
+            Ok(expected) => expected,
+            Err(err) => {
+                println!("golden {name}:");
+                print_error(&err);
+                return [None, None];
+            }
+        },
+        Err(_) => Expected::default(),
+    };
+
+    let mut options = options.clone();
+    options.input = Some(golden.to_path_buf());
+    options.params.splice(0..0, expected.params);
+
+    let report = runner.run(day, &options);
+
+    let mut line = format!("golden {name}:");
+    for (part, expected) in report.parts.iter().zip([&expected.a, &expected.b]) {
+        let Some(answer) = part.answer.as_ref().filter(|a| a.is_implemented()) else {
+            continue;
+        };
+
+        let answer = answer.to_string();
+        let check = match expected {
+            Some(expected) if *expected == answer => "✓".green(),
+            Some(expected) => format!("✗ (expected {expected})").red(),
+            None => "?".yellow(),
+        };
+        line += &format!(" {} {answer} {check}", part.part);
+    }
+    println!("{line}");
+
+    if let Some(err) = &report.error {
+        print_error(err);
+    }
+
+    answers(&report)
+}
+
+/// runs the day and its goldens whenever the input, a golden or an .expected file changes
+pub fn watch(day: u32, runner: DayRunner, options: &RunOptions, interval: Duration) -> ! {
+    let input = match &options.input {
+        Some(input) => input.clone(),
+        None => day::input_path(options.year, day),
+    };
+
+    let mut last_stamps = None;
+    let mut previous: HashMap<String, Answers> = HashMap::new();
+
+    loop {
+        let now = stamps(&input, options.year, day);
+        if last_stamps.as_ref() == Some(&now) {
+            thread::sleep(interval);
+            continue;
+        }
+
+        if last_stamps.is_some() {
+            println!(
+                "{}",
+                "======= change detected, rerunning =======".bright_blue()
+            );
+        }
+        last_stamps = Some(now);
+
+        let report = runner.run(day, options);
+        let mut printer = Printer::new(Format::Text);
+        printer.print(&report);
+        printer.finish();
+
+        let name = input.display().to_string();
+        let answers = answers(&report);
+        print_changes(&name, &answers, previous.get(&name));
+        previous.insert(name, answers);
+
+        for golden in goldens(options.year, day) {
+            let answers = check_golden(day, runner, options, &golden);
+            let name = golden.display().to_string();
+            print_changes(&name, &answers, previous.get(&name));
+            previous.insert(name, answers);
+        }
+
+        println!(
+            "{}",
+            format!(
+                "watching {} and {}/day{day}*",
+                input.display(),
+                day::goldens_dir(options.year).display()
+            )
+            .dimmed()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::MemorySource;
+    use crate::registry::Registry;
+    use crate::select::Parts;
+    use std::sync::Arc;
+
+    #[test]
+    fn golden_names() {
+        assert!(is_golden_of("day1", 1));
+        assert!(is_golden_of("day1-2", 1));
+        assert!(is_golden_of("day1-2.expected", 1));
+        assert!(!is_golden_of("day12", 1));
+        assert!(!is_golden_of("day2", 1));
+    }
+
+    #[test]
+    fn expected_file() {
+        let expected = parse_expected("# example 2\na = 11\nb=6,1\n\nsize = 6\n").unwrap();
+
+        assert_eq!(
+            expected,
+            Expected {
+                a: Some("11".to_string()),
+                b: Some("6,1".to_string()),
+                params: vec![("size".to_string(), 6)],
+            }
+        );
+
+        match parse_expected("a = 1\nsize = big\n") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (2, 8)),
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn goldens_give_their_expected_answers() {
+        let registry = Registry::collect();
+
+        for day in 1..=25 {
+            let runner = registry.get(2024, day).unwrap();
+            for golden in goldens(2024, day) {
+                let Ok(text) = fs::read_to_string(expected_path(&golden)) else {
+                    continue;
+                };
+                let expected = parse_expected(&text).unwrap();
+                let options = RunOptions {
+                    year: 2024,
+                    verbosity: 0,
+                    params: expected.params,
+                    input: Some(golden.clone()),
+                    timeout: None,
+                    parts: Parts::Both,
+                    source: Arc::new(MemorySource::default()),
+                };
+
+                let report = runner.run(day, &options);
+                for (part, expected) in report.parts.iter().zip([expected.a, expected.b]) {
+                    if let Some(expected) = expected {
+                        let answer = part.answer.as_ref().map(|a| a.to_string());
+                        assert_eq!(answer, Some(expected), "{}", golden.display());
+                    }
+                }
+            }
+        }
+    }
+}
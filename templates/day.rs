@@ -0,0 +1,48 @@
+use crate::answer::Answer;
+use crate::day::Solution;
+use crate::error::AocError;
+use crate::log::Log;
+use crate::params::Params;
+use crate::registry;
+use crate::utils;
+
+type Input = Vec<String>;
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    type Input = Input;
+
+    fn parse(&self, input: String, _log: &Log) -> Result<Input, AocError> {
+        Ok(utils::lines(&input)
+            .map(|line| line.text.to_string())
+            .collect())
+    }
+
+    fn part_a(&self, _input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        Ok(Answer::NotImplemented)
+    }
+
+    fn part_b(&self, _input: &Input, _params: &Params, _log: &Log) -> Result<Answer, AocError> {
+        Ok(Answer::NotImplemented)
+    }
+}
+
+registry::register!({year}, {day}, Day{day});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn goldens() {
+        utils::golden("{year}/day{day}", &Day{day}, None, None, false)
+    }
+
+    // drop the ignore once the answers are in answers/{year}.toml, record puts them there
+    #[test]
+    #[ignore = "needs the real input and the known answers"]
+    fn finalanswer() {
+        utils::finalanswer({year}, {day}, &Day{day}, false);
+    }
+}